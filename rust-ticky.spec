@@ -56,6 +56,18 @@ use the "%{crate}" crate.
 %doc %{crate_instdir}/SECURITY.md
 %{crate_instdir}/
 
+%package     -n %{name}+alloc-devel
+Summary:        %{summary}
+BuildArch:      noarch
+
+%description -n %{name}+alloc-devel %{_description}
+
+This package contains library source intended for building other packages which
+use the "alloc" feature of the "%{crate}" crate.
+
+%files       -n %{name}+alloc-devel
+%ghost %{crate_instdir}/Cargo.toml
+
 %package     -n %{name}+default-devel
 Summary:        %{summary}
 BuildArch:      noarch
@@ -80,6 +92,18 @@ use the "derive_more" feature of the "%{crate}" crate.
 %files       -n %{name}+derive_more-devel
 %ghost %{crate_instdir}/Cargo.toml
 
+%package     -n %{name}+futures-core-devel
+Summary:        %{summary}
+BuildArch:      noarch
+
+%description -n %{name}+futures-core-devel %{_description}
+
+This package contains library source intended for building other packages which
+use the "futures-core" feature of the "%{crate}" crate.
+
+%files       -n %{name}+futures-core-devel
+%ghost %{crate_instdir}/Cargo.toml
+
 %package     -n %{name}+hifitime-devel
 Summary:        %{summary}
 BuildArch:      noarch
@@ -92,6 +116,18 @@ use the "hifitime" feature of the "%{crate}" crate.
 %files       -n %{name}+hifitime-devel
 %ghost %{crate_instdir}/Cargo.toml
 
+%package     -n %{name}+libc-devel
+Summary:        %{summary}
+BuildArch:      noarch
+
+%description -n %{name}+libc-devel %{_description}
+
+This package contains library source intended for building other packages which
+use the "libc" feature of the "%{crate}" crate.
+
+%files       -n %{name}+libc-devel
+%ghost %{crate_instdir}/Cargo.toml
+
 %package     -n %{name}+std-devel
 Summary:        %{summary}
 BuildArch:      noarch
@@ -0,0 +1,258 @@
+/*
+	This file is part of Ticky.
+	Ticky is free software: you can redistribute it and/or modify
+	it under the terms of the GNU Affero General Public License as published by
+	the Free Software Foundation, either version 3 of the License, or
+	(at your option) any later version.
+	Ticky is distributed in the hope that it will be useful,
+	but WITHOUT ANY WARRANTY; without even the implied warranty of
+	MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+	GNU Affero General Public License for more details.
+	You should have received a copy of the GNU Affero General Public License
+	along with Ticky.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::{Clock, DefaultClock};
+use core::sync::atomic::{AtomicU64, Ordering};
+use core::time::Duration;
+
+/// The bit of the state that is set while the stopwatch is running.
+const RUNNING: u64 = 1 << 63;
+
+/// The bits of the state that hold a time, in nanoseconds.
+const NANOS: u64 = RUNNING - 1;
+
+#[derive(Debug)]
+/// A stopwatch that can be shared between threads without a lock.
+///
+/// Its whole state is kept in a single atomic integer, so it can be started, stopped, read, and reset through a shared reference.
+/// Elapsed times are kept to the nanosecond, and wrap around after roughly 292 years.
+///
+/// ## Usage
+/// ```rust
+/// use core::time::Duration;
+/// use ticky::{AtomicStopwatch, MockClock};
+///
+/// let clock = MockClock::new(); // Create a new mock clock
+/// let sw = AtomicStopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
+/// std::thread::scope(|s| {
+///     s.spawn(|| sw.start()); // Start the stopwatch on another thread
+/// });
+/// clock.advance(Duration::from_secs(2)); // Move time forward by 2 seconds
+/// std::thread::scope(|s| {
+///     s.spawn(|| sw.stop()); // Stop the stopwatch on yet another thread
+/// });
+/// assert_eq!(sw.elapsed(), Duration::from_secs(2));
+/// ```
+pub struct AtomicStopwatch<C: Clock = DefaultClock> {
+	/// While stopped, the total elapsed time; while running, the time since `origin` at which the stopwatch would have been started to have its current elapsed time.
+	state: AtomicU64,
+	/// The time that all other times are measured from.
+	origin: C::Instant,
+	/// The clock the stopwatch reads time from.
+	clock: C,
+}
+
+#[cfg(feature = "stdtime")]
+impl AtomicStopwatch<crate::StdClock> {
+	/// Creates a new stopwatch.
+	///
+	/// # Example
+	/// ```rust
+	/// use ticky::AtomicStopwatch;
+	///
+	/// let sw = AtomicStopwatch::new(); // Create a new stopwatch
+	/// assert_eq!(sw.is_running(), false); // The stopwatch is not running
+	/// ```
+	pub fn new() -> AtomicStopwatch<crate::StdClock> {
+		AtomicStopwatch::with_clock(crate::StdClock)
+	}
+
+	/// Creates a new stopwatch and starts it.
+	///
+	/// # Example
+	/// ```rust
+	/// use ticky::AtomicStopwatch;
+	///
+	/// let sw = AtomicStopwatch::start_new(); // Create a new stopwatch, and start it
+	/// assert_eq!(sw.is_running(), true); // The stopwatch is running
+	/// ```
+	pub fn start_new() -> AtomicStopwatch<crate::StdClock> {
+		let sw = AtomicStopwatch::new();
+		sw.start();
+		sw
+	}
+}
+
+#[cfg(feature = "stdtime")]
+impl Default for AtomicStopwatch<crate::StdClock> {
+	fn default() -> Self {
+		AtomicStopwatch::new()
+	}
+}
+
+impl<C: Clock<Duration = Duration>> AtomicStopwatch<C> {
+	/// Creates a new stopwatch that reads time from the given clock.
+	///
+	/// # Example
+	/// ```rust
+	/// use ticky::{AtomicStopwatch, MockClock};
+	///
+	/// let sw = AtomicStopwatch::with_clock(MockClock::new()); // Create a new stopwatch using a mock clock
+	/// assert_eq!(sw.is_running(), false); // The stopwatch is not running
+	/// ```
+	pub fn with_clock(clock: C) -> AtomicStopwatch<C> {
+		AtomicStopwatch {
+			state: AtomicU64::new(0),
+			origin: clock.now(),
+			clock,
+		}
+	}
+
+	/// Returns the time since `origin`, in nanoseconds.
+	fn now(&self) -> u64 {
+		let since_origin = self.clock.duration_between(self.origin, self.clock.now());
+		(since_origin.as_nanos() as u64) & NANOS
+	}
+
+	/// Starts (or resumes) the stopwatch.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{AtomicStopwatch, MockClock};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let sw = AtomicStopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
+	/// sw.start(); // Start the stopwatch
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// sw.stop(); // Stop the stopwatch (pausing it)
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// sw.start(); // Start the stopwatch again (resuming it)
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// assert_eq!(sw.elapsed(), Duration::from_secs(2));
+	/// ```
+	pub fn start(&self) {
+		let mut state = self.state.load(Ordering::Acquire);
+		while state & RUNNING == 0 {
+			let started = self.now().wrapping_sub(state) & NANOS;
+			match self.state.compare_exchange_weak(
+				state,
+				RUNNING | started,
+				Ordering::AcqRel,
+				Ordering::Acquire,
+			) {
+				Ok(_) => return,
+				Err(actual) => state = actual,
+			}
+		}
+	}
+
+	/// Stops (or pauses) the stopwatch.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{AtomicStopwatch, MockClock};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let sw = AtomicStopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
+	/// sw.start(); // Start the stopwatch
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// sw.stop(); // Stop the stopwatch
+	/// sw.stop(); // Stopping a stopped stopwatch does nothing
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// assert_eq!(sw.elapsed(), Duration::from_secs(1));
+	/// ```
+	pub fn stop(&self) {
+		let mut state = self.state.load(Ordering::Acquire);
+		while state & RUNNING != 0 {
+			let elapsed = self.now().wrapping_sub(state) & NANOS;
+			match self.state.compare_exchange_weak(
+				state,
+				elapsed,
+				Ordering::AcqRel,
+				Ordering::Acquire,
+			) {
+				Ok(_) => return,
+				Err(actual) => state = actual,
+			}
+		}
+	}
+
+	/// Returns the total elapsed time.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{AtomicStopwatch, MockClock};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let sw = AtomicStopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
+	/// sw.start(); // Start the stopwatch
+	/// clock.advance(Duration::from_millis(1_500)); // Move time forward by 1.5 seconds
+	/// assert_eq!(sw.elapsed(), Duration::from_millis(1_500));
+	/// ```
+	pub fn elapsed(&self) -> Duration {
+		let state = self.state.load(Ordering::Acquire);
+		match state & RUNNING != 0 {
+			true => Duration::from_nanos(self.now().wrapping_sub(state) & NANOS),
+			false => Duration::from_nanos(state),
+		}
+	}
+
+	/// Resets the stopwatch.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{AtomicStopwatch, MockClock};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let sw = AtomicStopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
+	/// sw.start(); // Start the stopwatch
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// sw.reset(); // Reset the stopwatch
+	/// assert_eq!(sw.is_running(), false); // The stopwatch is not running
+	/// assert_eq!(sw.elapsed(), Duration::ZERO);
+	/// ```
+	pub fn reset(&self) {
+		self.state.store(0, Ordering::Release);
+	}
+
+	/// Resets and starts the stopwatch.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{AtomicStopwatch, MockClock};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let sw = AtomicStopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
+	/// sw.start(); // Start the stopwatch
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// sw.restart(); // Reset and start the stopwatch
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// assert_eq!(sw.elapsed(), Duration::from_secs(1));
+	/// ```
+	pub fn restart(&self) {
+		self.state.store(RUNNING | self.now(), Ordering::Release);
+	}
+
+	/// Returns true if the stopwatch is running, and false if not.
+	///
+	/// # Example
+	/// ```rust
+	/// use ticky::{AtomicStopwatch, MockClock};
+	///
+	/// let sw = AtomicStopwatch::with_clock(MockClock::new()); // Create a new stopwatch using a mock clock
+	/// assert_eq!(sw.is_running(), false); // The stopwatch is not running
+	/// sw.start(); // Start the stopwatch
+	/// assert_eq!(sw.is_running(), true); // The stopwatch is running
+	/// sw.stop(); // Stop the stopwatch
+	/// assert_eq!(sw.is_running(), false); // The stopwatch is not running
+	/// ```
+	pub fn is_running(&self) -> bool {
+		self.state.load(Ordering::Acquire) & RUNNING != 0
+	}
+}
@@ -0,0 +1,264 @@
+/*
+	This file is part of Ticky.
+	Ticky is free software: you can redistribute it and/or modify
+	it under the terms of the GNU Affero General Public License as published by
+	the Free Software Foundation, either version 3 of the License, or
+	(at your option) any later version.
+	Ticky is distributed in the hope that it will be useful,
+	but WITHOUT ANY WARRANTY; without even the implied warranty of
+	MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+	GNU Affero General Public License for more details.
+	You should have received a copy of the GNU Affero General Public License
+	along with Ticky.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::{Clock, ClockDuration, Laps, Stopwatch};
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display, Formatter};
+
+#[derive(Eq, PartialEq, Clone, Debug, Hash)]
+/// A named point in a run of a [`Stopwatch`].
+pub struct Checkpoint<D> {
+	/// The name of the checkpoint; empty for laps recorded with [`Stopwatch::lap`] or [`Stopwatch::split`].
+	pub name: Cow<'static, str>,
+	/// The time between the previous checkpoint (or the start) and this one.
+	pub since_previous: D,
+	/// The time between the start and this checkpoint.
+	pub since_start: D,
+}
+
+#[derive(Eq, PartialEq, Clone, Debug, Hash)]
+/// The named checkpoints marked during a run of a [`Stopwatch`].
+///
+/// Formatting the checkpoints with `{}` produces a table of the time between consecutive checkpoints, and from the start to each one.
+///
+/// ## Usage
+/// ```rust
+/// use core::time::Duration;
+/// use ticky::{Checkpoints, MockClock, Stopwatch};
+///
+/// let clock = MockClock::new(); // Create a new mock clock
+/// let mut sw = Stopwatch::with_clock(clock.clone()).with_laps(Checkpoints::new()); // Create a new stopwatch that records checkpoints
+/// sw.start(); // Start the stopwatch
+/// clock.advance(Duration::from_millis(1_500)); // Move time forward by 1.5 seconds
+/// sw.checkpoint("parsed"); // Mark a checkpoint
+/// clock.advance(Duration::from_millis(250)); // Move time forward by 250 milliseconds
+/// sw.checkpoint("validated"); // Mark another checkpoint
+/// clock.advance(Duration::from_secs(2)); // Move time forward by 2 seconds
+/// sw.checkpoint("written"); // Mark a final checkpoint
+///
+/// let validated = sw.checkpoints().get("validated").unwrap();
+/// assert_eq!(validated.since_previous, Duration::from_millis(250));
+/// assert_eq!(validated.since_start, Duration::from_millis(1_750));
+/// assert_eq!(
+///     sw.checkpoints().to_string(),
+///     "checkpoint  since previous  since start\n\
+///      parsed                1.5s         1.5s\n\
+///      validated            250ms        1.75s\n\
+///      written                 2s        3.75s"
+/// );
+/// ```
+pub struct Checkpoints<D> {
+	/// The checkpoints, in the order they were marked.
+	checkpoints: Vec<Checkpoint<D>>,
+}
+
+impl<D: ClockDuration> Checkpoints<D> {
+	/// Creates a new, empty set of checkpoints.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::Checkpoints;
+	///
+	/// let checkpoints = Checkpoints::<Duration>::new(); // Create a new set of checkpoints
+	/// assert_eq!(checkpoints.iter().count(), 0); // No checkpoints have been marked
+	/// ```
+	pub const fn new() -> Checkpoints<D> {
+		Checkpoints {
+			checkpoints: Vec::new(),
+		}
+	}
+
+	/// Adds a checkpoint, given the time since the previous one.
+	fn push(&mut self, name: Cow<'static, str>, since_previous: D) {
+		let since_start = match self.checkpoints.last() {
+			Some(previous) => previous.since_start + since_previous,
+			None => since_previous,
+		};
+		self.checkpoints.push(Checkpoint {
+			name,
+			since_previous,
+			since_start,
+		});
+	}
+
+	/// Returns the checkpoints, in the order they were marked.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{Checkpoints, MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()).with_laps(Checkpoints::new()); // Create a new stopwatch that records checkpoints
+	/// sw.start(); // Start the stopwatch
+	/// sw.checkpoint("parsed"); // Mark a checkpoint
+	/// sw.checkpoint("validated"); // Mark another checkpoint
+	/// let names: Vec<_> = sw.checkpoints().iter().map(|checkpoint| checkpoint.name.as_ref()).collect();
+	/// assert_eq!(names, ["parsed", "validated"]);
+	/// ```
+	pub fn iter(&self) -> core::slice::Iter<'_, Checkpoint<D>> {
+		self.checkpoints.iter()
+	}
+
+	/// Returns the first checkpoint with the given name, if any.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{Checkpoints, MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()).with_laps(Checkpoints::new()); // Create a new stopwatch that records checkpoints
+	/// sw.start(); // Start the stopwatch
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// sw.checkpoint("parsed"); // Mark a checkpoint
+	/// assert_eq!(sw.checkpoints().get("parsed").map(|checkpoint| checkpoint.since_start), Some(Duration::from_secs(1)));
+	/// assert_eq!(sw.checkpoints().get("written"), None);
+	/// ```
+	pub fn get(&self, name: &str) -> Option<&Checkpoint<D>> {
+		self.checkpoints
+			.iter()
+			.find(|checkpoint| checkpoint.name == name)
+	}
+}
+
+impl<D: ClockDuration> Default for Checkpoints<D> {
+	fn default() -> Self {
+		Checkpoints::new()
+	}
+}
+
+impl<'a, D> IntoIterator for &'a Checkpoints<D> {
+	type Item = &'a Checkpoint<D>;
+	type IntoIter = core::slice::Iter<'a, Checkpoint<D>>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.checkpoints.iter()
+	}
+}
+
+impl<D: ClockDuration> Laps<D> for Checkpoints<D> {
+	fn record(&mut self, lap: D) {
+		self.push(Cow::Borrowed(""), lap);
+	}
+
+	fn clear(&mut self) {
+		self.checkpoints.clear();
+	}
+
+	fn laps(&self) -> impl Iterator<Item = D> {
+		self.checkpoints
+			.iter()
+			.map(|checkpoint| checkpoint.since_previous)
+	}
+
+	fn last_lap(&self) -> Option<D> {
+		self.checkpoints
+			.last()
+			.map(|checkpoint| checkpoint.since_previous)
+	}
+
+	fn lap_count(&self) -> usize {
+		self.checkpoints.len()
+	}
+
+	fn nth_fastest_lap(&self, n: usize) -> Option<D>
+	where
+		D: Ord,
+	{
+		let mut laps: Vec<D> = self.laps().collect();
+		(n < laps.len()).then(|| *laps.select_nth_unstable(n).1)
+	}
+
+	fn splits(&self) -> impl Iterator<Item = D> {
+		self.checkpoints
+			.iter()
+			.map(|checkpoint| checkpoint.since_start)
+	}
+}
+
+impl<D: Debug> Display for Checkpoints<D> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		let rows: Vec<(&str, String, String)> = self
+			.checkpoints
+			.iter()
+			.map(|checkpoint| {
+				(
+					checkpoint.name.as_ref(),
+					format!("{:?}", checkpoint.since_previous),
+					format!("{:?}", checkpoint.since_start),
+				)
+			})
+			.collect();
+		let (mut name_width, mut previous_width, mut start_width) = (10, 14, 11);
+		for (name, since_previous, since_start) in &rows {
+			name_width = name_width.max(name.chars().count());
+			previous_width = previous_width.max(since_previous.chars().count());
+			start_width = start_width.max(since_start.chars().count());
+		}
+		write!(
+			f,
+			"{:<name_width$}  {:>previous_width$}  {:>start_width$}",
+			"checkpoint", "since previous", "since start"
+		)?;
+		for (name, since_previous, since_start) in &rows {
+			write!(
+				f,
+				"\n{name:<name_width$}  {since_previous:>previous_width$}  {since_start:>start_width$}"
+			)?;
+		}
+		Ok(())
+	}
+}
+
+impl<C: Clock> Stopwatch<C, Checkpoints<C::Duration>> {
+	/// Marks a named checkpoint, returning the time since the previous checkpoint (or the start).
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{Checkpoints, MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()).with_laps(Checkpoints::new()); // Create a new stopwatch that records checkpoints
+	/// sw.start(); // Start the stopwatch
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// assert_eq!(sw.checkpoint("parsed"), Duration::from_secs(1));
+	/// clock.advance(Duration::from_secs(2)); // Move time forward by 2 seconds
+	/// assert_eq!(sw.checkpoint(format!("stage {}", 2)), Duration::from_secs(2)); // Names can be built at runtime
+	/// ```
+	pub fn checkpoint(&mut self, name: impl Into<Cow<'static, str>>) -> C::Duration {
+		let lap = self.end_lap();
+		self.laps.push(name.into(), lap);
+		lap
+	}
+
+	/// Returns the checkpoints marked so far.
+	///
+	/// # Example
+	/// ```rust
+	/// use ticky::{Checkpoints, MockClock, Stopwatch};
+	///
+	/// let mut sw = Stopwatch::with_clock(MockClock::new()).with_laps(Checkpoints::new()); // Create a new stopwatch that records checkpoints
+	/// sw.checkpoint("parsed"); // Mark a checkpoint
+	/// assert_eq!(sw.checkpoints().iter().count(), 1);
+	/// ```
+	pub fn checkpoints(&self) -> &Checkpoints<C::Duration> {
+		&self.laps
+	}
+}
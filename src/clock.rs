@@ -0,0 +1,409 @@
+/*
+	This file is part of Ticky.
+	Ticky is free software: you can redistribute it and/or modify
+	it under the terms of the GNU Affero General Public License as published by
+	the Free Software Foundation, either version 3 of the License, or
+	(at your option) any later version.
+	Ticky is distributed in the hope that it will be useful,
+	but WITHOUT ANY WARRANTY; without even the implied warranty of
+	MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+	GNU Affero General Public License for more details.
+	You should have received a copy of the GNU Affero General Public License
+	along with Ticky.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::TickyError;
+use core::ops::{Add, AddAssign};
+
+/// A source of time that a [`Stopwatch`](crate::Stopwatch) can measure with.
+///
+/// # Example
+/// ```rust
+/// use core::cell::Cell;
+/// use core::time::Duration;
+/// use ticky::{Clock, Stopwatch};
+///
+/// /// A clock that moves forward by one second every time it is read.
+/// #[derive(Default)]
+/// struct SteppingClock(Cell<u64>);
+///
+/// impl Clock for SteppingClock {
+///     type Instant = u64;
+///     type Duration = Duration;
+///
+///     fn now(&self) -> u64 {
+///         self.0.set(self.0.get() + 1);
+///         self.0.get()
+///     }
+///
+///     fn duration_between(&self, earlier: u64, later: u64) -> Duration {
+///         Duration::from_secs(later - earlier)
+///     }
+/// }
+///
+/// let mut sw = Stopwatch::with_clock(SteppingClock::default()); // Create a new stopwatch using the custom clock
+/// sw.start(); // Start the stopwatch
+/// sw.stop(); // Stop the stopwatch
+/// assert_eq!(sw.elapsed(), Duration::from_secs(1)); // The clock moved forward once between starting and stopping
+/// ```
+pub trait Clock {
+	/// A point in time, as reported by this clock.
+	type Instant: Copy;
+
+	/// The difference between two points in time, as reported by this clock.
+	type Duration: ClockDuration;
+
+	/// Returns the current point in time.
+	fn now(&self) -> Self::Instant;
+
+	/// Returns the current point in time, or an error if the clock could not be read.
+	///
+	/// Clocks that cannot fail do not need to implement this; by default, it returns the result of [`now`](Clock::now).
+	fn try_now(&self) -> Result<Self::Instant, TickyError> {
+		Ok(self.now())
+	}
+
+	/// Returns the amount of time that passed between `earlier` and `later`.
+	fn duration_between(&self, earlier: Self::Instant, later: Self::Instant) -> Self::Duration;
+
+	/// Returns the amount of time that passed between `earlier` and `later`, or an error if it could not be measured reliably.
+	///
+	/// Clocks that cannot fail do not need to implement this; by default, it returns the result of [`duration_between`](Clock::duration_between).
+	fn try_duration_between(
+		&self,
+		earlier: Self::Instant,
+		later: Self::Instant,
+	) -> Result<Self::Duration, TickyError> {
+		Ok(self.duration_between(earlier, later))
+	}
+}
+
+/// A duration type that a [`Clock`] can report.
+pub trait ClockDuration: Copy + Add<Output = Self> + AddAssign {
+	/// A duration of no time.
+	const ZERO: Self;
+
+	/// Adds two durations, returning `None` if the result cannot be represented.
+	fn checked_add(self, rhs: Self) -> Option<Self>;
+}
+
+impl ClockDuration for core::time::Duration {
+	const ZERO: Self = core::time::Duration::ZERO;
+
+	fn checked_add(self, rhs: Self) -> Option<Self> {
+		core::time::Duration::checked_add(self, rhs)
+	}
+}
+
+#[cfg(feature = "hifitime")]
+impl ClockDuration for hifitime::Duration {
+	const ZERO: Self = hifitime::Duration::ZERO;
+
+	fn checked_add(self, rhs: Self) -> Option<Self> {
+		let nanos = self
+			.total_nanoseconds()
+			.checked_add(rhs.total_nanoseconds())?;
+		let range = hifitime::Duration::MIN.total_nanoseconds()
+			..=hifitime::Duration::MAX.total_nanoseconds();
+		range
+			.contains(&nanos)
+			.then(|| hifitime::Duration::from_total_nanoseconds(nanos))
+	}
+}
+
+/// A [`ClockDuration`] that is a single amount of time, and so can be expressed in any [`Unit`](crate::Unit).
+pub trait ScalarDuration: ClockDuration {
+	/// Returns the duration in whole nanoseconds, negative if the duration is negative.
+	fn total_nanoseconds(&self) -> i128;
+
+	/// Creates a duration from whole nanoseconds, saturating at the smallest and largest durations that can be represented.
+	fn from_total_nanoseconds(nanos: i128) -> Self;
+}
+
+impl ScalarDuration for core::time::Duration {
+	fn total_nanoseconds(&self) -> i128 {
+		self.as_nanos() as i128
+	}
+
+	fn from_total_nanoseconds(nanos: i128) -> Self {
+		let secs = nanos.div_euclid(1_000_000_000);
+		let nanos = nanos.rem_euclid(1_000_000_000) as u32;
+		match u64::try_from(secs) {
+			Ok(secs) => core::time::Duration::new(secs, nanos),
+			Err(_) if secs < 0 => core::time::Duration::ZERO,
+			Err(_) => core::time::Duration::MAX,
+		}
+	}
+}
+
+#[cfg(feature = "hifitime")]
+impl ScalarDuration for hifitime::Duration {
+	fn total_nanoseconds(&self) -> i128 {
+		hifitime::Duration::total_nanoseconds(self)
+	}
+
+	fn from_total_nanoseconds(nanos: i128) -> Self {
+		hifitime::Duration::from_total_nanoseconds(nanos)
+	}
+}
+
+#[cfg(feature = "stdtime")]
+#[derive(Ord, Eq, PartialEq, PartialOrd, Clone, Copy, Debug, Hash, Default)]
+/// A clock backed by [`std::time::Instant`].
+pub struct StdClock;
+
+#[cfg(feature = "stdtime")]
+impl Clock for StdClock {
+	type Instant = std::time::Instant;
+	type Duration = core::time::Duration;
+
+	fn now(&self) -> Self::Instant {
+		std::time::Instant::now()
+	}
+
+	fn duration_between(&self, earlier: Self::Instant, later: Self::Instant) -> Self::Duration {
+		later.duration_since(earlier)
+	}
+}
+
+#[cfg(all(feature = "hifitime", feature = "std"))]
+#[derive(Ord, Eq, PartialEq, PartialOrd, Clone, Copy, Debug, Hash)]
+/// A clock backed by [`hifitime::Epoch`].
+///
+/// [`hifitime::Epoch::now`] reads the system's wall clock, which can be stepped backwards or forwards (for example, by NTP). To guard against this, every reading is paired with a reading of the system's monotonic clock.
+///
+/// In monotonic mode (the default), durations are measured with the monotonic clock, so adjustments to the wall clock do not affect them. In wall-clock mode, durations are measured with the wall clock, and a backwards step is measured as no time passing.
+///
+/// In either mode, [`try_duration_between`](Clock::try_duration_between) (and so [`Stopwatch::try_stop`](crate::Stopwatch::try_stop) and [`Stopwatch::try_elapsed`](crate::Stopwatch::try_elapsed)) returns an error if the two clocks disagree by more than [`jump_tolerance`](HifiClock::jump_tolerance).
+///
+/// ## Usage
+/// ```rust
+/// use hifitime::{Duration, Epoch};
+/// use ticky::{Clock, HifiClock, HifiInstant, TickyError};
+///
+/// let earlier = HifiClock::monotonic().now(); // Read the clock
+/// let later = HifiInstant {
+///     epoch: earlier.epoch - Duration::from_seconds(10.0), // The wall clock was stepped back by 10 seconds …
+///     instant: earlier.instant + std::time::Duration::from_secs(1), // … while 1 second actually passed
+/// };
+/// assert_eq!(HifiClock::monotonic().duration_between(earlier, later), Duration::from_seconds(1.0));
+/// assert_eq!(HifiClock::wall().duration_between(earlier, later), Duration::ZERO);
+/// assert_eq!(HifiClock::monotonic().try_duration_between(earlier, later), Err(TickyError::ClockJumpedBackward));
+/// ```
+pub struct HifiClock {
+	/// Whether durations are measured with the monotonic clock rather than the wall clock.
+	pub monotonic: bool,
+	/// How far the wall clock may drift from the monotonic clock before it is considered to have jumped.
+	pub jump_tolerance: core::time::Duration,
+}
+
+#[cfg(all(feature = "hifitime", feature = "std"))]
+#[derive(Ord, Eq, PartialEq, PartialOrd, Clone, Copy, Debug, Hash)]
+/// A reading of a [`HifiClock`].
+pub struct HifiInstant {
+	/// The time according to the wall clock.
+	pub epoch: hifitime::Epoch,
+	/// The time according to the monotonic clock.
+	pub instant: std::time::Instant,
+}
+
+#[cfg(all(feature = "hifitime", feature = "std"))]
+impl Default for HifiClock {
+	fn default() -> Self {
+		HifiClock::monotonic()
+	}
+}
+
+#[cfg(all(feature = "hifitime", feature = "std"))]
+impl HifiClock {
+	/// Creates a new clock that measures durations with the monotonic clock.
+	///
+	/// # Example
+	/// ```rust
+	/// use ticky::HifiClock;
+	///
+	/// let clock = HifiClock::monotonic(); // Create a new monotonic clock
+	/// assert_eq!(clock.monotonic, true);
+	/// ```
+	pub const fn monotonic() -> HifiClock {
+		HifiClock {
+			monotonic: true,
+			jump_tolerance: core::time::Duration::from_millis(100),
+		}
+	}
+
+	/// Creates a new clock that measures durations with the wall clock.
+	///
+	/// # Example
+	/// ```rust
+	/// use ticky::HifiClock;
+	///
+	/// let clock = HifiClock::wall(); // Create a new wall clock
+	/// assert_eq!(clock.monotonic, false);
+	/// ```
+	pub const fn wall() -> HifiClock {
+		HifiClock {
+			monotonic: false,
+			..HifiClock::monotonic()
+		}
+	}
+
+	/// Returns how far the wall clock moved beyond the monotonic clock between two readings.
+	///
+	/// A positive result means the wall clock was stepped forwards, and a negative result means it was stepped backwards.
+	///
+	/// # Example
+	/// ```rust
+	/// use hifitime::Duration;
+	/// use ticky::{Clock, HifiClock, HifiInstant};
+	///
+	/// let clock = HifiClock::default(); // Create a new clock
+	/// let earlier = clock.now(); // Read the clock
+	/// let later = HifiInstant {
+	///     epoch: earlier.epoch + Duration::from_seconds(5.0), // The wall clock was stepped forward by 4 seconds …
+	///     instant: earlier.instant + std::time::Duration::from_secs(1), // … while 1 second actually passed
+	/// };
+	/// assert_eq!(clock.jump_between(earlier, later), Duration::from_seconds(4.0));
+	/// ```
+	pub fn jump_between(&self, earlier: HifiInstant, later: HifiInstant) -> hifitime::Duration {
+		(later.epoch - earlier.epoch) - monotonic_between(earlier, later)
+	}
+}
+
+#[cfg(all(feature = "hifitime", feature = "std"))]
+impl Clock for HifiClock {
+	type Instant = HifiInstant;
+	type Duration = hifitime::Duration;
+
+	fn now(&self) -> Self::Instant {
+		self.try_now().unwrap()
+	}
+
+	fn try_now(&self) -> Result<Self::Instant, TickyError> {
+		Ok(HifiInstant {
+			epoch: hifitime::Epoch::now().map_err(|_| TickyError::ClockUnavailable)?,
+			instant: std::time::Instant::now(),
+		})
+	}
+
+	fn duration_between(&self, earlier: Self::Instant, later: Self::Instant) -> Self::Duration {
+		match self.monotonic {
+			true => monotonic_between(earlier, later),
+			false => (later.epoch - earlier.epoch).max(hifitime::Duration::ZERO),
+		}
+	}
+
+	fn try_duration_between(
+		&self,
+		earlier: Self::Instant,
+		later: Self::Instant,
+	) -> Result<Self::Duration, TickyError> {
+		let jump = self.jump_between(earlier, later);
+		let tolerance = hifitime::Duration::from(self.jump_tolerance);
+		if jump > tolerance {
+			Err(TickyError::ClockJumpedForward)
+		} else if jump < -tolerance {
+			Err(TickyError::ClockJumpedBackward)
+		} else {
+			Ok(self.duration_between(earlier, later))
+		}
+	}
+}
+
+/// Returns the time that passed between two readings of a [`HifiClock`], according to the monotonic clock.
+#[cfg(all(feature = "hifitime", feature = "std"))]
+fn monotonic_between(earlier: HifiInstant, later: HifiInstant) -> hifitime::Duration {
+	later.instant.duration_since(earlier.instant).into()
+}
+
+#[derive(Clone, Copy, Debug)]
+/// A clock backed by a monotonic tick counter, for use without the standard library.
+///
+/// ## Usage
+/// ```rust
+/// use core::sync::atomic::{AtomicU64, Ordering};
+/// use core::time::Duration;
+/// use ticky::{Stopwatch, TickClock};
+///
+/// static TICKS: AtomicU64 = AtomicU64::new(0); // A stand-in for a hardware counter
+///
+/// fn read_ticks() -> u64 {
+///     TICKS.load(Ordering::SeqCst)
+/// }
+///
+/// let mut sw = Stopwatch::with_clock(TickClock::new(read_ticks, 1_000)); // Create a new stopwatch using a 1 kHz tick counter
+/// sw.start(); // Start the stopwatch
+/// TICKS.fetch_add(1_500, Ordering::SeqCst); // Let 1,500 ticks pass
+/// sw.stop(); // Stop the stopwatch
+/// assert_eq!(sw.elapsed(), Duration::from_millis(1_500));
+/// ```
+pub struct TickClock {
+	/// Reads the current value of the tick counter.
+	pub ticks: fn() -> u64,
+	/// How many times the tick counter increments per second.
+	pub ticks_per_second: u64,
+}
+
+impl TickClock {
+	/// Creates a new clock from a tick counter and its frequency.
+	///
+	/// The counter must never go backwards, other than by wrapping around [`u64::MAX`], and `ticks_per_second` must not be zero.
+	///
+	/// # Example
+	/// ```rust
+	/// use ticky::TickClock;
+	///
+	/// fn read_ticks() -> u64 {
+	///     42 // Read a hardware counter here
+	/// }
+	///
+	/// let clock = TickClock::new(read_ticks, 32_768); // Create a new clock from a 32.768 kHz counter
+	/// assert_eq!(clock.ticks_per_second, 32_768);
+	/// ```
+	pub const fn new(ticks: fn() -> u64, ticks_per_second: u64) -> TickClock {
+		TickClock {
+			ticks,
+			ticks_per_second,
+		}
+	}
+}
+
+impl Clock for TickClock {
+	type Instant = u64;
+	type Duration = core::time::Duration;
+
+	fn now(&self) -> Self::Instant {
+		(self.ticks)()
+	}
+
+	fn duration_between(&self, earlier: Self::Instant, later: Self::Instant) -> Self::Duration {
+		let ticks = later.wrapping_sub(earlier);
+		let secs = ticks / self.ticks_per_second;
+		let remainder = u128::from(ticks % self.ticks_per_second);
+		let nanos = remainder * 1_000_000_000 / u128::from(self.ticks_per_second);
+		core::time::Duration::new(secs, nanos as u32)
+	}
+}
+
+cfg_if::cfg_if! {
+	if #[cfg(feature = "stdtime")] {
+		/// The clock used by [`Stopwatch`](crate::Stopwatch) when none is specified.
+		///
+		/// This is [`StdClock`] whenever the `stdtime` feature is enabled, even if `hifitime` is enabled too.
+		pub type DefaultClock = StdClock;
+
+		/// The clock used by [`Stopwatch::new`](crate::Stopwatch::new).
+		pub(crate) const DEFAULT_CLOCK: DefaultClock = StdClock;
+	} else if #[cfg(all(feature = "hifitime", feature = "std"))] {
+		/// The clock used by [`Stopwatch`](crate::Stopwatch) when none is specified.
+		pub type DefaultClock = HifiClock;
+
+		/// The clock used by [`Stopwatch::new`](crate::Stopwatch::new).
+		pub(crate) const DEFAULT_CLOCK: DefaultClock = HifiClock::monotonic();
+	} else {
+		/// The clock used by [`Stopwatch`](crate::Stopwatch) when none is specified.
+		///
+		/// Without a standard library time source, there is no way to read the time automatically, so the tick counter must be supplied with [`Stopwatch::with_clock`](crate::Stopwatch::with_clock).
+		pub type DefaultClock = TickClock;
+	}
+}
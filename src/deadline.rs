@@ -0,0 +1,263 @@
+/*
+	This file is part of Ticky.
+	Ticky is free software: you can redistribute it and/or modify
+	it under the terms of the GNU Affero General Public License as published by
+	the Free Software Foundation, either version 3 of the License, or
+	(at your option) any later version.
+	Ticky is distributed in the hope that it will be useful,
+	but WITHOUT ANY WARRANTY; without even the implied warranty of
+	MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+	GNU Affero General Public License for more details.
+	You should have received a copy of the GNU Affero General Public License
+	along with Ticky.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::{Clock, DefaultClock, ScalarDuration, Timer};
+use core::fmt::{Debug, Formatter};
+
+/// A point in time by which an operation should finish, set from a time budget.
+///
+/// A deadline can be divided into smaller deadlines for the steps of an operation, each given part of the time that is left. Converting a deadline into a [`Duration`](core::time::Duration) (or, with the `hifitime` feature, a `hifitime::Duration`) gives the time remaining, to pass to APIs that take a timeout.
+///
+/// ## Usage
+/// ```rust
+/// use core::time::Duration;
+/// use ticky::{Deadline, MockClock};
+///
+/// let clock = MockClock::new(); // Create a new mock clock
+/// let request = Deadline::after_with_clock(Duration::from_secs(10), clock.clone()); // Give the request 10 seconds
+/// clock.advance(Duration::from_secs(5)); // Spend 5 seconds handling the request
+/// let query = request.fraction(0.4); // Give the database query 40% of what is left
+/// assert_eq!(query.remaining(), Duration::from_secs(2));
+/// clock.advance(Duration::from_secs(3)); // The query takes 3 seconds
+/// assert_eq!(query.is_expired(), true); // The query missed its deadline …
+/// assert_eq!(request.is_expired(), false); // … but the request still has time left
+/// let timeout: Duration = request.into(); // Convert the time left into a timeout
+/// assert_eq!(timeout, Duration::from_secs(2));
+/// ```
+pub struct Deadline<C: Clock = DefaultClock> {
+	/// The timer counting down to the deadline.
+	timer: Timer<C>,
+}
+
+impl<C: Clock> Clone for Deadline<C>
+where
+	Timer<C>: Clone,
+{
+	fn clone(&self) -> Self {
+		Deadline {
+			timer: self.timer.clone(),
+		}
+	}
+}
+
+impl<C: Clock> Copy for Deadline<C> where Timer<C>: Copy {}
+
+impl<C: Clock> Debug for Deadline<C>
+where
+	Timer<C>: Debug,
+{
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("Deadline")
+			.field("timer", &self.timer)
+			.finish()
+	}
+}
+
+#[cfg(any(feature = "stdtime", all(feature = "hifitime", feature = "std")))]
+impl Deadline {
+	/// Creates a deadline the given time budget from now.
+	///
+	/// # Example
+	/// ```rust
+	/// use std::time::Duration;
+	/// use ticky::Deadline;
+	///
+	/// let deadline = Deadline::after(Duration::from_millis(100)); // Set a deadline 100 milliseconds from now
+	/// assert_eq!(deadline.is_expired(), false);
+	/// std::thread::sleep(Duration::from_millis(200)); // Wait for 200 milliseconds
+	/// assert_eq!(deadline.is_expired(), true);
+	/// ```
+	pub fn after(budget: <DefaultClock as Clock>::Duration) -> Deadline {
+		Deadline::after_with_clock(budget, crate::DEFAULT_CLOCK)
+	}
+}
+
+impl<C: Clock> Deadline<C> {
+	/// Creates a deadline the given time budget from now, reading time from the given clock.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{Deadline, MockClock};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let deadline = Deadline::after_with_clock(Duration::from_secs(10), clock.clone()); // Set a deadline 10 seconds from now
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// assert_eq!(deadline.remaining(), Duration::from_secs(9));
+	/// ```
+	pub fn after_with_clock(budget: C::Duration, clock: C) -> Deadline<C> {
+		let mut timer = Timer::with_clock(budget, clock);
+		timer.resume();
+		Deadline { timer }
+	}
+
+	/// Returns the time budget the deadline was set from.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{Deadline, MockClock};
+	///
+	/// let deadline = Deadline::after_with_clock(Duration::from_secs(10), MockClock::new()); // Set a deadline 10 seconds from now
+	/// assert_eq!(deadline.budget(), Duration::from_secs(10));
+	/// ```
+	pub fn budget(&self) -> C::Duration {
+		self.timer.duration
+	}
+
+	/// Returns the time since the deadline was set.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{Deadline, MockClock};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let deadline = Deadline::after_with_clock(Duration::from_secs(10), clock.clone()); // Set a deadline 10 seconds from now
+	/// clock.advance(Duration::from_secs(12)); // Move time forward by 12 seconds
+	/// assert_eq!(deadline.elapsed(), Duration::from_secs(12)); // The elapsed time keeps counting after the deadline passes
+	/// ```
+	pub fn elapsed(&self) -> C::Duration {
+		self.timer.elapsed()
+	}
+}
+
+impl<C: Clock> Deadline<C>
+where
+	C::Duration: ScalarDuration,
+{
+	/// Returns the time left before the deadline, or zero if it has passed.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{Deadline, MockClock};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let deadline = Deadline::after_with_clock(Duration::from_secs(10), clock.clone()); // Set a deadline 10 seconds from now
+	/// clock.advance(Duration::from_secs(4)); // Move time forward by 4 seconds
+	/// assert_eq!(deadline.remaining(), Duration::from_secs(6));
+	/// clock.advance(Duration::from_secs(10)); // Move time forward past the deadline
+	/// assert_eq!(deadline.remaining(), Duration::ZERO);
+	/// ```
+	pub fn remaining(&self) -> C::Duration {
+		self.timer.remaining()
+	}
+
+	/// Returns whether the deadline has passed.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{Deadline, MockClock};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let deadline = Deadline::after_with_clock(Duration::from_secs(10), clock.clone()); // Set a deadline 10 seconds from now
+	/// clock.advance(Duration::from_secs(9)); // Move time forward by 9 seconds
+	/// assert_eq!(deadline.is_expired(), false);
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// assert_eq!(deadline.is_expired(), true); // The deadline passes as soon as no time is left
+	/// ```
+	pub fn is_expired(&self) -> bool {
+		self.timer.is_expired()
+	}
+
+	/// Returns how long ago the deadline passed, or zero if it has not passed.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{Deadline, MockClock};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let deadline = Deadline::after_with_clock(Duration::from_secs(10), clock.clone()); // Set a deadline 10 seconds from now
+	/// clock.advance(Duration::from_secs(12)); // Move time forward by 12 seconds
+	/// assert_eq!(deadline.overrun(), Duration::from_secs(2));
+	/// ```
+	pub fn overrun(&self) -> C::Duration {
+		self.timer.overrun()
+	}
+
+	/// Creates a deadline from now, with the given fraction (from 0 to 1) of the time left before this one.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{Deadline, MockClock};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let request = Deadline::after_with_clock(Duration::from_secs(10), clock.clone()); // Set a deadline 10 seconds from now
+	/// assert_eq!(request.fraction(0.25).remaining(), Duration::from_millis(2_500));
+	/// assert_eq!(request.fraction(2.0).remaining(), Duration::from_secs(10)); // A step never gets more time than is left
+	/// ```
+	pub fn fraction(&self, fraction: f64) -> Deadline<C>
+	where
+		C: Clone,
+	{
+		let nanos = self.remaining().total_nanoseconds() as f64 * fraction.clamp(0.0, 1.0);
+		Deadline::after_with_clock(
+			C::Duration::from_total_nanoseconds(nanos as i128),
+			self.timer.stopwatch.clock.clone(),
+		)
+	}
+
+	/// Creates a deadline from now, with the given time budget or the time left before this one, whichever is shorter.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{Deadline, MockClock};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let request = Deadline::after_with_clock(Duration::from_secs(10), clock.clone()); // Set a deadline 10 seconds from now
+	/// assert_eq!(request.capped(Duration::from_secs(3)).remaining(), Duration::from_secs(3));
+	/// clock.advance(Duration::from_secs(8)); // Move time forward by 8 seconds
+	/// assert_eq!(request.capped(Duration::from_secs(3)).remaining(), Duration::from_secs(2)); // Only 2 seconds are left
+	/// ```
+	pub fn capped(&self, budget: C::Duration) -> Deadline<C>
+	where
+		C: Clone,
+	{
+		let nanos = budget
+			.total_nanoseconds()
+			.min(self.remaining().total_nanoseconds());
+		Deadline::after_with_clock(
+			C::Duration::from_total_nanoseconds(nanos),
+			self.timer.stopwatch.clock.clone(),
+		)
+	}
+}
+
+impl<C: Clock> From<Deadline<C>> for core::time::Duration
+where
+	C::Duration: ScalarDuration,
+{
+	fn from(deadline: Deadline<C>) -> Self {
+		<core::time::Duration as ScalarDuration>::from_total_nanoseconds(
+			deadline.remaining().total_nanoseconds(),
+		)
+	}
+}
+
+#[cfg(feature = "hifitime")]
+impl<C: Clock> From<Deadline<C>> for hifitime::Duration
+where
+	C::Duration: ScalarDuration,
+{
+	fn from(deadline: Deadline<C>) -> Self {
+		<hifitime::Duration as ScalarDuration>::from_total_nanoseconds(
+			deadline.remaining().total_nanoseconds(),
+		)
+	}
+}
@@ -0,0 +1,44 @@
+/*
+	This file is part of Ticky.
+	Ticky is free software: you can redistribute it and/or modify
+	it under the terms of the GNU Affero General Public License as published by
+	the Free Software Foundation, either version 3 of the License, or
+	(at your option) any later version.
+	Ticky is distributed in the hope that it will be useful,
+	but WITHOUT ANY WARRANTY; without even the implied warranty of
+	MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+	GNU Affero General Public License for more details.
+	You should have received a copy of the GNU Affero General Public License
+	along with Ticky.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use core::fmt::{Display, Formatter};
+
+#[derive(Ord, Eq, PartialEq, PartialOrd, Clone, Copy, Debug, Hash)]
+/// An error that occurred while measuring time.
+pub enum TickyError {
+	/// The clock could not be read.
+	ClockUnavailable,
+	/// A duration was negative where only non-negative durations make sense.
+	NegativeDuration,
+	/// A duration was too large to be represented.
+	Overflow,
+	/// The clock was stepped backwards while measuring.
+	ClockJumpedBackward,
+	/// The clock was stepped forwards while measuring.
+	ClockJumpedForward,
+}
+
+impl Display for TickyError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		match self {
+			TickyError::ClockUnavailable => write!(f, "the clock could not be read"),
+			TickyError::NegativeDuration => write!(f, "the duration is negative"),
+			TickyError::Overflow => write!(f, "the duration is too large to be represented"),
+			TickyError::ClockJumpedBackward => write!(f, "the clock was stepped backwards"),
+			TickyError::ClockJumpedForward => write!(f, "the clock was stepped forwards"),
+		}
+	}
+}
+
+impl core::error::Error for TickyError {}
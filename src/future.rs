@@ -0,0 +1,172 @@
+/*
+	This file is part of Ticky.
+	Ticky is free software: you can redistribute it and/or modify
+	it under the terms of the GNU Affero General Public License as published by
+	the Free Software Foundation, either version 3 of the License, or
+	(at your option) any later version.
+	Ticky is distributed in the hope that it will be useful,
+	but WITHOUT ANY WARRANTY; without even the implied warranty of
+	MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+	GNU Affero General Public License for more details.
+	You should have received a copy of the GNU Affero General Public License
+	along with Ticky.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::{Clock, ClockDuration, DefaultClock};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+#[derive(Ord, Eq, PartialEq, PartialOrd, Clone, Copy, Debug, Hash)]
+/// How long a [`Timed`] future took to complete.
+pub struct FutureTiming<D> {
+	/// The time from the first poll until the future completed, including time spent waiting to be polled again.
+	pub wall: D,
+	/// The time spent inside the future's `poll` method.
+	pub busy: D,
+	/// The number of times the future was polled.
+	pub polls: u64,
+}
+
+/// A future that measures how long the future it wraps takes to complete, created with [`TimedFutureExt::timed`].
+///
+/// When the wrapped future completes, its output is returned alongside a [`FutureTiming`]. A large gap between the wall time and the busy time means the future spent most of its time waiting, while a busy time close to the wall time means it kept its executor busy.
+///
+/// ## Usage
+/// ```rust
+/// use core::future::{poll_fn, Future};
+/// use core::pin::pin;
+/// use core::task::{Context, Poll, Waker};
+/// use core::time::Duration;
+/// use ticky::{MockClock, TimedFutureExt};
+///
+/// let clock = MockClock::new(); // Create a new mock clock
+/// let mut ready = false;
+/// let work = poll_fn(|cx| {
+///     clock.advance(Duration::from_millis(10)); // Spend 10 milliseconds busy in each poll
+///     match ready {
+///         true => Poll::Ready(42),
+///         false => {
+///             ready = true;
+///             cx.waker().wake_by_ref();
+///             Poll::Pending
+///         }
+///     }
+/// });
+/// let mut timed = pin!(work.timed_with_clock(clock.clone())); // Time the future
+/// let mut cx = Context::from_waker(Waker::noop());
+/// assert!(timed.as_mut().poll(&mut cx).is_pending());
+/// clock.advance(Duration::from_millis(100)); // Wait 100 milliseconds before polling again
+/// let Poll::Ready((answer, timing)) = timed.as_mut().poll(&mut cx) else {
+///     panic!("the future should have completed");
+/// };
+/// assert_eq!(answer, 42);
+/// assert_eq!(timing.wall, Duration::from_millis(120));
+/// assert_eq!(timing.busy, Duration::from_millis(20));
+/// assert_eq!(timing.polls, 2);
+/// ```
+pub struct Timed<F, C: Clock = DefaultClock> {
+	/// The future being timed.
+	future: F,
+	/// The clock the future is timed with.
+	clock: C,
+	/// The time at which the future was first polled, if it has been polled.
+	first_poll: Option<C::Instant>,
+	/// The time spent inside the future's `poll` method so far.
+	busy: C::Duration,
+	/// The number of times the future has been polled so far.
+	polls: u64,
+}
+
+impl<F, C: Clock> Timed<F, C> {
+	/// Returns the time spent inside the wrapped future's `poll` method so far.
+	pub fn busy(&self) -> C::Duration {
+		self.busy
+	}
+
+	/// Returns the number of times the wrapped future has been polled so far.
+	pub fn polls(&self) -> u64 {
+		self.polls
+	}
+}
+
+impl<F: Future, C: Clock> Future for Timed<F, C> {
+	type Output = (F::Output, FutureTiming<C::Duration>);
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		// SAFETY: `future` is never moved out of `self`, and no other field is pinned.
+		let this = unsafe { self.get_unchecked_mut() };
+		let start = this.clock.now();
+		let first_poll = *this.first_poll.get_or_insert(start);
+		// SAFETY: `this` is pinned, so `future` is too.
+		let poll = unsafe { Pin::new_unchecked(&mut this.future) }.poll(cx);
+		let end = this.clock.now();
+		this.busy += this.clock.duration_between(start, end);
+		this.polls += 1;
+		match poll {
+			Poll::Ready(output) => Poll::Ready((
+				output,
+				FutureTiming {
+					wall: this.clock.duration_between(first_poll, end),
+					busy: this.busy,
+					polls: this.polls,
+				},
+			)),
+			Poll::Pending => Poll::Pending,
+		}
+	}
+}
+
+/// Adds methods for timing futures.
+pub trait TimedFutureExt: Future + Sized {
+	#[cfg(any(feature = "stdtime", all(feature = "hifitime", feature = "std")))]
+	/// Wraps the future so that it reports how long it took to complete, measured with the [`DefaultClock`].
+	///
+	/// # Example
+	/// ```rust
+	/// use core::future::{ready, Future};
+	/// use core::pin::pin;
+	/// use core::task::{Context, Poll, Waker};
+	/// use ticky::TimedFutureExt;
+	///
+	/// let mut timed = pin!(ready(42).timed()); // Time a future
+	/// let mut cx = Context::from_waker(Waker::noop());
+	/// let Poll::Ready((answer, timing)) = timed.as_mut().poll(&mut cx) else {
+	///     panic!("the future should have completed");
+	/// };
+	/// assert_eq!(answer, 42);
+	/// assert_eq!(timing.polls, 1); // The future completed on its first poll
+	/// ```
+	fn timed(self) -> Timed<Self> {
+		self.timed_with_clock(crate::DEFAULT_CLOCK)
+	}
+
+	/// Wraps the future so that it reports how long it took to complete, measured with the given clock.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::future::{ready, Future};
+	/// use core::pin::pin;
+	/// use core::task::{Context, Poll, Waker};
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, TimedFutureExt};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut timed = pin!(ready(42).timed_with_clock(clock.clone())); // Time a future with the mock clock
+	/// assert_eq!(timed.polls(), 0); // The future has not been polled yet
+	/// let mut cx = Context::from_waker(Waker::noop());
+	/// assert!(timed.as_mut().poll(&mut cx).is_ready());
+	/// assert_eq!(timed.busy(), Duration::ZERO); // No time passed on the mock clock
+	/// ```
+	fn timed_with_clock<C: Clock>(self, clock: C) -> Timed<Self, C> {
+		Timed {
+			future: self,
+			clock,
+			first_poll: None,
+			busy: C::Duration::ZERO,
+			polls: 0,
+		}
+	}
+}
+
+impl<F: Future> TimedFutureExt for F {}
@@ -0,0 +1,402 @@
+/*
+	This file is part of Ticky.
+	Ticky is free software: you can redistribute it and/or modify
+	it under the terms of the GNU Affero General Public License as published by
+	the Free Software Foundation, either version 3 of the License, or
+	(at your option) any later version.
+	Ticky is distributed in the hope that it will be useful,
+	but WITHOUT ANY WARRANTY; without even the implied warranty of
+	MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+	GNU Affero General Public License for more details.
+	You should have received a copy of the GNU Affero General Public License
+	along with Ticky.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::{ScalarDuration, TickyError};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::time::Duration;
+
+#[derive(Eq, PartialEq, Clone, Debug, Hash)]
+/// A histogram of durations, in the style of [HdrHistogram](http://hdrhistogram.org/).
+///
+/// Durations are counted in buckets whose width grows with the duration, so that every duration is kept to a fixed number of significant figures.
+/// The memory used is fixed when the histogram is created, and depends only on its precision and range.
+///
+/// ## Usage
+/// ```rust
+/// use core::time::Duration;
+/// use ticky::{LatencyHistogram, MockClock, Stopwatch};
+///
+/// let clock = MockClock::new(); // Create a new mock clock
+/// let mut hist = LatencyHistogram::new(Duration::from_secs(60), 3); // Track up to 1 minute, to 3 significant figures
+/// for millis in 1..=1_000 {
+///     let mut sw = Stopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
+///     sw.start(); // Start the stopwatch
+///     clock.advance(Duration::from_millis(millis)); // Move time forward
+///     sw.stop(); // Stop the stopwatch
+///     hist.record(sw.elapsed())?; // Record the measurement
+/// }
+/// assert_eq!(hist.count(), 1_000);
+/// assert_eq!(hist.value_at_percentile(50.0), Duration::from_nanos(500_170_751)); // Within 0.1% of 500 milliseconds
+/// assert_eq!(hist.value_at_percentile(99.0), Duration::from_nanos(990_380_031)); // Within 0.1% of 990 milliseconds
+/// assert_eq!(hist.value_at_percentile(99.9), Duration::from_nanos(999_292_927)); // Within 0.1% of 999 milliseconds
+/// assert_eq!(hist.max(), Duration::from_secs(1));
+/// # Ok::<(), ticky::TickyError>(())
+/// ```
+pub struct LatencyHistogram {
+	/// The number of durations counted in each bucket.
+	counts: Vec<u64>,
+	/// The longest duration that can be recorded, in nanoseconds.
+	highest_trackable: u64,
+	/// The base-2 logarithm of half the number of sub-buckets in each bucket.
+	sub_bucket_half_count_magnitude: u32,
+	/// A mask covering the bits of a duration that select a sub-bucket in the first bucket.
+	sub_bucket_mask: u64,
+	/// The number of durations recorded.
+	total_count: u64,
+	/// The sum of all durations recorded, in nanoseconds.
+	total_nanos: u128,
+	/// The shortest duration recorded, in nanoseconds.
+	min: u64,
+	/// The longest duration recorded, in nanoseconds.
+	max: u64,
+}
+
+impl LatencyHistogram {
+	/// Creates a new, empty histogram that can record durations up to `highest_trackable`, keeping them to the given number of significant figures.
+	///
+	/// # Panics
+	/// Panics if more than 5 significant figures are requested.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::LatencyHistogram;
+	///
+	/// let hist = LatencyHistogram::new(Duration::from_secs(60), 3); // Track up to 1 minute, to 3 significant figures
+	/// assert_eq!(hist.count(), 0); // No durations have been recorded
+	/// ```
+	pub fn new(highest_trackable: Duration, significant_figures: u8) -> LatencyHistogram {
+		assert!(
+			significant_figures <= 5,
+			"a histogram can keep at most 5 significant figures"
+		);
+		let highest_trackable = u64::try_from(highest_trackable.as_nanos()).unwrap_or(u64::MAX);
+		let largest_with_single_unit_resolution = 2 * 10u64.pow(significant_figures.into());
+		let sub_bucket_count_magnitude =
+			u64::BITS - (largest_with_single_unit_resolution - 1).leading_zeros();
+		let sub_bucket_half_count_magnitude = sub_bucket_count_magnitude.max(1) - 1;
+		let sub_bucket_count = 1u64 << (sub_bucket_half_count_magnitude + 1);
+		let mut bucket_count = 1;
+		let mut smallest_untrackable = sub_bucket_count;
+		while smallest_untrackable <= highest_trackable {
+			bucket_count += 1;
+			if smallest_untrackable > u64::MAX / 2 {
+				break;
+			}
+			smallest_untrackable <<= 1;
+		}
+		LatencyHistogram {
+			counts: vec![0; (bucket_count + 1) << sub_bucket_half_count_magnitude],
+			highest_trackable,
+			sub_bucket_half_count_magnitude,
+			sub_bucket_mask: sub_bucket_count - 1,
+			total_count: 0,
+			total_nanos: 0,
+			min: u64::MAX,
+			max: 0,
+		}
+	}
+
+	/// Records a duration, or returns an error if it is negative or longer than the histogram can track.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{LatencyHistogram, TickyError};
+	///
+	/// let mut hist = LatencyHistogram::new(Duration::from_secs(1), 3); // Track up to 1 second, to 3 significant figures
+	/// assert_eq!(hist.record(Duration::from_millis(20)), Ok(()));
+	/// assert_eq!(hist.record(Duration::from_secs(2)), Err(TickyError::Overflow)); // Too long to be tracked
+	/// assert_eq!(hist.count(), 1);
+	/// ```
+	pub fn record<D: ScalarDuration>(&mut self, dur: D) -> Result<(), TickyError> {
+		self.record_nanos(nanos(dur)?, 1)
+	}
+
+	/// Records a duration measured at a regular interval, correcting for coordinated omission, or returns an error if it is negative or longer than the histogram can track.
+	///
+	/// When a measurement takes longer than the interval between measurements, the measurements that should have started in the meantime were delayed, and would have seen longer durations.
+	/// Those measurements are recorded as well, so that long pauses are not understated.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::LatencyHistogram;
+	///
+	/// let mut hist = LatencyHistogram::new(Duration::from_secs(1), 3); // Track up to 1 second, to 3 significant figures
+	/// let interval = Duration::from_millis(10); // A request is sent every 10 milliseconds
+	/// hist.record_corrected(Duration::from_millis(40), interval)?; // One request stalled for 40 milliseconds
+	/// assert_eq!(hist.count(), 4); // The 3 requests that would have been sent during the stall are recorded too
+	/// assert_eq!(hist.min(), Duration::from_millis(10));
+	/// # Ok::<(), ticky::TickyError>(())
+	/// ```
+	pub fn record_corrected<D: ScalarDuration>(
+		&mut self,
+		dur: D,
+		expected_interval: Duration,
+	) -> Result<(), TickyError> {
+		let value = nanos(dur)?;
+		self.record_nanos(value, 1)?;
+		let interval = u64::try_from(expected_interval.as_nanos()).unwrap_or(u64::MAX);
+		if interval == 0 {
+			return Ok(());
+		}
+		let mut missing = value.saturating_sub(interval);
+		while missing >= interval {
+			self.record_nanos(missing, 1)?;
+			missing -= interval;
+		}
+		Ok(())
+	}
+
+	/// Adds every duration recorded in another histogram to this one, or returns an error if any of them are longer than this histogram can track.
+	///
+	/// The histograms may have different ranges and precisions; if they do, durations are recorded to the precision of the coarser of the two.
+	/// If an error is returned, this histogram is left unchanged.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::LatencyHistogram;
+	///
+	/// let mut first = LatencyHistogram::new(Duration::from_secs(1), 3); // Create a histogram for one worker
+	/// let mut second = first.clone(); // Create an identical histogram for another worker
+	/// first.record(Duration::from_millis(5))?; // Record a duration on the first worker
+	/// second.record(Duration::from_millis(7))?; // Record a duration on the second worker
+	/// first.merge(&second)?; // Combine the histograms
+	/// assert_eq!(first.count(), 2);
+	/// assert_eq!(first.max(), Duration::from_millis(7));
+	/// # Ok::<(), ticky::TickyError>(())
+	/// ```
+	pub fn merge(&mut self, other: &LatencyHistogram) -> Result<(), TickyError> {
+		if other.total_count == 0 {
+			return Ok(());
+		}
+		if other.max > self.highest_trackable {
+			return Err(TickyError::Overflow);
+		}
+		if self.counts.len() == other.counts.len()
+			&& self.sub_bucket_half_count_magnitude == other.sub_bucket_half_count_magnitude
+		{
+			for (count, other_count) in self.counts.iter_mut().zip(&other.counts) {
+				*count = count.saturating_add(*other_count);
+			}
+		} else {
+			for (index, &count) in other.counts.iter().enumerate() {
+				if count != 0 {
+					let index_here = self.index_of(other.value_from_index(index).min(other.max));
+					self.counts[index_here] = self.counts[index_here].saturating_add(count);
+				}
+			}
+		}
+		self.total_count = self.total_count.saturating_add(other.total_count);
+		self.total_nanos = self.total_nanos.saturating_add(other.total_nanos);
+		self.min = self.min.min(other.min);
+		self.max = self.max.max(other.max);
+		Ok(())
+	}
+
+	/// Forgets all recorded durations.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::LatencyHistogram;
+	///
+	/// let mut hist = LatencyHistogram::new(Duration::from_secs(1), 3); // Track up to 1 second, to 3 significant figures
+	/// hist.record(Duration::from_millis(20))?; // Record a duration
+	/// hist.reset(); // Forget it
+	/// assert_eq!(hist.count(), 0);
+	/// # Ok::<(), ticky::TickyError>(())
+	/// ```
+	pub fn reset(&mut self) {
+		self.counts.fill(0);
+		self.total_count = 0;
+		self.total_nanos = 0;
+		self.min = u64::MAX;
+		self.max = 0;
+	}
+
+	/// Returns the number of durations recorded.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::LatencyHistogram;
+	///
+	/// let mut hist = LatencyHistogram::new(Duration::from_secs(1), 3); // Track up to 1 second, to 3 significant figures
+	/// hist.record(Duration::from_millis(20))?; // Record a duration
+	/// hist.record(Duration::from_millis(20))?; // Record the same duration again
+	/// assert_eq!(hist.count(), 2);
+	/// # Ok::<(), ticky::TickyError>(())
+	/// ```
+	pub fn count(&self) -> u64 {
+		self.total_count
+	}
+
+	/// Returns the longest duration the histogram can record.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::LatencyHistogram;
+	///
+	/// let hist = LatencyHistogram::new(Duration::from_secs(1), 3); // Track up to 1 second, to 3 significant figures
+	/// assert_eq!(hist.highest_trackable(), Duration::from_secs(1));
+	/// ```
+	pub fn highest_trackable(&self) -> Duration {
+		Duration::from_nanos(self.highest_trackable)
+	}
+
+	/// Returns the shortest duration recorded, exactly, or zero if none have been recorded.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::LatencyHistogram;
+	///
+	/// let mut hist = LatencyHistogram::new(Duration::from_secs(1), 3); // Track up to 1 second, to 3 significant figures
+	/// hist.record(Duration::from_nanos(123_456_789))?; // Record a duration
+	/// hist.record(Duration::from_millis(200))?; // Record a longer duration
+	/// assert_eq!(hist.min(), Duration::from_nanos(123_456_789));
+	/// # Ok::<(), ticky::TickyError>(())
+	/// ```
+	pub fn min(&self) -> Duration {
+		match self.total_count {
+			0 => Duration::ZERO,
+			_ => Duration::from_nanos(self.min),
+		}
+	}
+
+	/// Returns the longest duration recorded, exactly, or zero if none have been recorded.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::LatencyHistogram;
+	///
+	/// let mut hist = LatencyHistogram::new(Duration::from_secs(1), 3); // Track up to 1 second, to 3 significant figures
+	/// hist.record(Duration::from_millis(100))?; // Record a duration
+	/// hist.record(Duration::from_nanos(123_456_789))?; // Record a longer duration
+	/// assert_eq!(hist.max(), Duration::from_nanos(123_456_789));
+	/// # Ok::<(), ticky::TickyError>(())
+	/// ```
+	pub fn max(&self) -> Duration {
+		Duration::from_nanos(self.max)
+	}
+
+	/// Returns the mean of the durations recorded, exactly (to the nanosecond), or zero if none have been recorded.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::LatencyHistogram;
+	///
+	/// let mut hist = LatencyHistogram::new(Duration::from_secs(1), 3); // Track up to 1 second, to 3 significant figures
+	/// hist.record(Duration::from_millis(100))?; // Record a duration
+	/// hist.record(Duration::from_millis(200))?; // Record a longer duration
+	/// assert_eq!(hist.mean(), Duration::from_millis(150));
+	/// # Ok::<(), ticky::TickyError>(())
+	/// ```
+	pub fn mean(&self) -> Duration {
+		match self.total_count {
+			0 => Duration::ZERO,
+			count => Duration::from_nanos((self.total_nanos / u128::from(count)) as u64),
+		}
+	}
+
+	/// Returns the duration at the given percentile (from 0 to 100) of the durations recorded, or zero if none have been recorded.
+	///
+	/// The rank of the percentile among the recorded durations is rounded to the nearest whole number. The duration returned is the longest that is equivalent, to the histogram's precision, to the duration at that percentile, and never longer than the longest duration recorded.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::LatencyHistogram;
+	///
+	/// let mut hist = LatencyHistogram::new(Duration::from_secs(1), 3); // Track up to 1 second, to 3 significant figures
+	/// for micros in 1..=100 {
+	///     hist.record(Duration::from_micros(micros))?; // Record a duration
+	/// }
+	/// assert_eq!(hist.value_at_percentile(50.0), Duration::from_nanos(50_015)); // Within 0.1% of 50 microseconds
+	/// assert_eq!(hist.value_at_percentile(100.0), Duration::from_micros(100));
+	/// # Ok::<(), ticky::TickyError>(())
+	/// ```
+	pub fn value_at_percentile(&self, percentile: f64) -> Duration {
+		let position = percentile.clamp(0.0, 100.0) / 100.0 * self.total_count as f64;
+		let rank = ((position + 0.5) as u64).max(1);
+		let mut seen = 0u64;
+		for (index, &count) in self.counts.iter().enumerate() {
+			seen = seen.saturating_add(count);
+			if seen >= rank {
+				let highest_equivalent = self.highest_equivalent(self.value_from_index(index));
+				return Duration::from_nanos(highest_equivalent.min(self.max));
+			}
+		}
+		Duration::ZERO
+	}
+
+	/// Counts a duration, in nanoseconds, the given number of times.
+	fn record_nanos(&mut self, value: u64, count: u64) -> Result<(), TickyError> {
+		if value > self.highest_trackable {
+			return Err(TickyError::Overflow);
+		}
+		let index = self.index_of(value);
+		self.counts[index] = self.counts[index].saturating_add(count);
+		self.total_count = self.total_count.saturating_add(count);
+		self.total_nanos = self
+			.total_nanos
+			.saturating_add(u128::from(value) * u128::from(count));
+		self.min = self.min.min(value);
+		self.max = self.max.max(value);
+		Ok(())
+	}
+
+	/// Returns the index of the bucket counting the given duration, in nanoseconds.
+	fn index_of(&self, value: u64) -> usize {
+		let bucket =
+			self.sub_bucket_mask.leading_zeros() - (value | self.sub_bucket_mask).leading_zeros();
+		let sub_bucket = value >> bucket;
+		(((bucket as u64 + 1) << self.sub_bucket_half_count_magnitude) + sub_bucket
+			- (1 << self.sub_bucket_half_count_magnitude)) as usize
+	}
+
+	/// Returns the shortest duration, in nanoseconds, counted by the bucket at the given index.
+	fn value_from_index(&self, index: usize) -> u64 {
+		let half_count = 1u64 << self.sub_bucket_half_count_magnitude;
+		let index = index as u64;
+		match (index >> self.sub_bucket_half_count_magnitude).checked_sub(1) {
+			Some(bucket) => ((index & (half_count - 1)) + half_count) << bucket,
+			None => index & (half_count - 1),
+		}
+	}
+
+	/// Returns the longest duration, in nanoseconds, counted by the same bucket as the given duration.
+	fn highest_equivalent(&self, value: u64) -> u64 {
+		let bucket =
+			self.sub_bucket_mask.leading_zeros() - (value | self.sub_bucket_mask).leading_zeros();
+		((value >> bucket) << bucket) + ((1 << bucket) - 1)
+	}
+}
+
+/// Converts a duration to whole nanoseconds, or returns an error if it is negative or too long.
+fn nanos<D: ScalarDuration>(dur: D) -> Result<u64, TickyError> {
+	let nanos = dur.total_nanoseconds();
+	match u64::try_from(nanos) {
+		Ok(nanos) => Ok(nanos),
+		Err(_) if nanos < 0 => Err(TickyError::NegativeDuration),
+		Err(_) => Err(TickyError::Overflow),
+	}
+}
@@ -0,0 +1,175 @@
+/*
+	This file is part of Ticky.
+	Ticky is free software: you can redistribute it and/or modify
+	it under the terms of the GNU Affero General Public License as published by
+	the Free Software Foundation, either version 3 of the License, or
+	(at your option) any later version.
+	Ticky is distributed in the hope that it will be useful,
+	but WITHOUT ANY WARRANTY; without even the implied warranty of
+	MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+	GNU Affero General Public License for more details.
+	You should have received a copy of the GNU Affero General Public License
+	along with Ticky.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::{Clock, DefaultClock, Stopwatch};
+
+#[derive(Ord, Eq, PartialEq, PartialOrd, Clone, Copy, Debug, Hash)]
+/// A summary of how long an iterator or stream took to produce its items.
+pub struct TimingSummary<D> {
+	/// The total time spent producing items, including the time taken to find that there were no more.
+	pub total: D,
+	/// The number of items produced.
+	pub count: usize,
+	/// The position and production time of the item that took longest to produce, if any were produced.
+	pub slowest: Option<(usize, D)>,
+}
+
+/// Times how long each item of an iterator or stream takes to produce.
+pub(crate) struct ItemTimer<C: Clock> {
+	/// The stopwatch timing the items, running while an item is being produced.
+	stopwatch: Stopwatch<C>,
+	/// The number of items produced so far.
+	count: usize,
+	/// The position and production time of the slowest item so far.
+	slowest: Option<(usize, C::Duration)>,
+}
+
+impl<C: Clock> ItemTimer<C>
+where
+	C::Duration: Ord,
+{
+	/// Creates a new item timer that reads time from the given clock.
+	pub(crate) const fn new(clock: C) -> ItemTimer<C> {
+		ItemTimer {
+			stopwatch: Stopwatch::with_clock(clock),
+			count: 0,
+			slowest: None,
+		}
+	}
+
+	/// Starts timing the next item, unless it is already being timed.
+	pub(crate) fn begin(&mut self) {
+		if !self.stopwatch.is_running() {
+			self.stopwatch.start();
+		}
+	}
+
+	/// Stops timing the current item, returning how long it took to produce.
+	pub(crate) fn finish(&mut self, produced: bool) -> C::Duration {
+		self.stopwatch.stop();
+		let time = self.stopwatch.lap();
+		if produced {
+			if self.slowest.is_none_or(|(_, slowest)| time > slowest) {
+				self.slowest = Some((self.count, time));
+			}
+			self.count += 1;
+		}
+		time
+	}
+
+	/// Returns a summary of the items produced so far.
+	pub(crate) fn summary(&self) -> TimingSummary<C::Duration> {
+		TimingSummary {
+			total: self.stopwatch.elapsed(),
+			count: self.count,
+			slowest: self.slowest,
+		}
+	}
+}
+
+/// An iterator that yields each item of the iterator it wraps alongside how long it took to produce, created with [`TimedIteratorExt::timed`].
+///
+/// ## Usage
+/// ```rust
+/// use core::time::Duration;
+/// use ticky::{MockClock, TimedIteratorExt};
+///
+/// let clock = MockClock::new(); // Create a new mock clock
+/// let records = [30, 10, 250, 20].into_iter().map(|millis| {
+///     clock.advance(Duration::from_millis(millis)); // Each record takes a different time to process
+///     millis
+/// });
+/// let mut timed = records.timed_with_clock(clock.clone()); // Time each record
+/// for (record, time) in timed.by_ref() {
+///     assert_eq!(time, Duration::from_millis(record));
+/// }
+/// let summary = timed.summary(); // Summarise once the records are exhausted
+/// assert_eq!(summary.count, 4);
+/// assert_eq!(summary.total, Duration::from_millis(310));
+/// assert_eq!(summary.slowest, Some((2, Duration::from_millis(250)))); // The third record was the slowest
+/// ```
+pub struct TimedIter<I, C: Clock = DefaultClock> {
+	/// The iterator being timed.
+	iter: I,
+	/// The timer for the iterator's items.
+	timer: ItemTimer<C>,
+}
+
+impl<I, C: Clock> TimedIter<I, C>
+where
+	C::Duration: Ord,
+{
+	/// Returns a summary of the items produced so far.
+	pub fn summary(&self) -> TimingSummary<C::Duration> {
+		self.timer.summary()
+	}
+}
+
+impl<I: Iterator, C: Clock> Iterator for TimedIter<I, C>
+where
+	C::Duration: Ord,
+{
+	type Item = (I::Item, C::Duration);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.timer.begin();
+		let item = self.iter.next();
+		let time = self.timer.finish(item.is_some());
+		item.map(|item| (item, time))
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+/// Adds methods for timing iterators.
+pub trait TimedIteratorExt: Iterator + Sized {
+	#[cfg(any(feature = "stdtime", all(feature = "hifitime", feature = "std")))]
+	/// Wraps the iterator so that it yields each item alongside how long it took to produce, measured with the [`DefaultClock`].
+	///
+	/// # Example
+	/// ```rust
+	/// use ticky::TimedIteratorExt;
+	///
+	/// let mut timed = (0..3).map(|_| std::thread::sleep(std::time::Duration::from_millis(10))).timed(); // Time each item
+	/// assert!(timed.by_ref().all(|(_, time)| time.as_millis() >= 10)); // Each item slept for at least 10 milliseconds
+	/// assert_eq!(timed.summary().count, 3);
+	/// ```
+	fn timed(self) -> TimedIter<Self> {
+		self.timed_with_clock(crate::DEFAULT_CLOCK)
+	}
+
+	/// Wraps the iterator so that it yields each item alongside how long it took to produce, measured with the given clock.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, TimedIteratorExt};
+	///
+	/// let mut timed = "a,b".split(',').timed_with_clock(MockClock::new()); // Time each item with a mock clock
+	/// assert_eq!(timed.next(), Some(("a", Duration::ZERO))); // No time passed on the mock clock
+	/// ```
+	fn timed_with_clock<C: Clock>(self, clock: C) -> TimedIter<Self, C>
+	where
+		C::Duration: Ord,
+	{
+		TimedIter {
+			iter: self,
+			timer: ItemTimer::new(clock),
+		}
+	}
+}
+
+impl<I: Iterator> TimedIteratorExt for I {}
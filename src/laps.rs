@@ -0,0 +1,487 @@
+/*
+	This file is part of Ticky.
+	Ticky is free software: you can redistribute it and/or modify
+	it under the terms of the GNU Affero General Public License as published by
+	the Free Software Foundation, either version 3 of the License, or
+	(at your option) any later version.
+	Ticky is distributed in the hope that it will be useful,
+	but WITHOUT ANY WARRANTY; without even the implied warranty of
+	MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+	GNU Affero General Public License for more details.
+	You should have received a copy of the GNU Affero General Public License
+	along with Ticky.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::{Clock, ClockDuration, DefaultClock, Stopwatch};
+
+/// Somewhere a [`Stopwatch`] can record its laps.
+///
+/// `()` keeps no laps, so laps can be timed without being recorded. A [`LapBuffer`] keeps a fixed number of the most recent laps without allocating. With the `alloc` feature, a `Vec` keeps every lap.
+///
+/// # Example
+/// ```rust
+/// use core::time::Duration;
+/// use ticky::{Laps, MockClock, Stopwatch};
+///
+/// #[derive(Default)]
+/// struct LapTotal(Duration); // Keep only the total of all laps
+///
+/// impl Laps<Duration> for LapTotal {
+///     fn record(&mut self, lap: Duration) {
+///         self.0 += lap;
+///     }
+///
+///     fn clear(&mut self) {
+///         self.0 = Duration::ZERO;
+///     }
+///
+///     fn laps(&self) -> impl Iterator<Item = Duration> {
+///         core::iter::empty()
+///     }
+/// }
+///
+/// let clock = MockClock::new(); // Create a new mock clock
+/// let mut sw = Stopwatch::with_clock(clock.clone()).with_laps(LapTotal::default()); // Create a new stopwatch that totals its laps
+/// sw.start(); // Start the stopwatch
+/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+/// sw.lap(); // Record a lap
+/// clock.advance(Duration::from_secs(2)); // Move time forward by 2 seconds
+/// sw.lap(); // Record a lap
+/// assert_eq!(sw.laps.0, Duration::from_secs(3));
+/// ```
+pub trait Laps<D: ClockDuration> {
+	/// Records a lap.
+	fn record(&mut self, lap: D);
+
+	/// Forgets all recorded laps.
+	fn clear(&mut self);
+
+	/// Returns the recorded laps that are still kept, oldest first.
+	fn laps(&self) -> impl Iterator<Item = D>;
+
+	/// Returns the most recently recorded lap, if any.
+	fn last_lap(&self) -> Option<D> {
+		self.laps().last()
+	}
+
+	/// Returns the number of laps recorded, including any that are no longer kept.
+	fn lap_count(&self) -> usize {
+		self.laps().count()
+	}
+
+	/// Returns the shortest lap recorded, including any that are no longer kept, if any.
+	fn fastest_lap(&self) -> Option<D>
+	where
+		D: Ord,
+	{
+		self.laps().min()
+	}
+
+	/// Returns the longest lap recorded, including any that are no longer kept, if any.
+	fn slowest_lap(&self) -> Option<D>
+	where
+		D: Ord,
+	{
+		self.laps().max()
+	}
+
+	/// Returns the `n`th fastest kept lap, counting from zero, if that many laps are kept.
+	///
+	/// This is the lap that would be at index `n` if the kept laps were sorted from fastest to slowest.
+	fn nth_fastest_lap(&self, n: usize) -> Option<D>
+	where
+		D: Ord,
+	{
+		self.laps().find(|lap| {
+			let faster = self.laps().filter(|other| other < lap).count();
+			let as_fast = self.laps().filter(|other| other <= lap).count();
+			(faster..as_fast).contains(&n)
+		})
+	}
+
+	/// Returns the split time at the end of each kept lap (the total time of all laps up to and including that lap), oldest first.
+	fn splits(&self) -> impl Iterator<Item = D> {
+		self.laps().scan(D::ZERO, |split, lap| {
+			*split += lap;
+			Some(*split)
+		})
+	}
+}
+
+impl<D: ClockDuration> Laps<D> for () {
+	fn record(&mut self, _lap: D) {}
+
+	fn clear(&mut self) {}
+
+	fn laps(&self) -> impl Iterator<Item = D> {
+		core::iter::empty()
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<D: ClockDuration> Laps<D> for alloc::vec::Vec<D> {
+	fn record(&mut self, lap: D) {
+		self.push(lap);
+	}
+
+	fn clear(&mut self) {
+		alloc::vec::Vec::clear(self);
+	}
+
+	fn laps(&self) -> impl Iterator<Item = D> {
+		self.iter().copied()
+	}
+
+	fn last_lap(&self) -> Option<D> {
+		self.last().copied()
+	}
+
+	fn lap_count(&self) -> usize {
+		self.len()
+	}
+
+	fn nth_fastest_lap(&self, n: usize) -> Option<D>
+	where
+		D: Ord,
+	{
+		(n < self.len()).then(|| *self.clone().select_nth_unstable(n).1)
+	}
+}
+
+/// A stopwatch that keeps its last `N` laps without allocating.
+///
+/// # Example
+/// ```rust
+/// use core::time::Duration;
+/// use ticky::{LapBuffer, LapStopwatch, MockClock, Stopwatch};
+///
+/// let clock = MockClock::new(); // Create a new mock clock
+/// let mut sw: LapStopwatch<2, MockClock> = Stopwatch::with_clock(clock.clone()).with_laps(LapBuffer::new()); // Create a new stopwatch that keeps its last 2 laps
+/// sw.start(); // Start the stopwatch
+/// for secs in [3, 1, 2] {
+///     clock.advance(Duration::from_secs(secs)); // Move time forward
+///     sw.lap(); // Record a lap
+/// }
+/// assert_eq!(sw.laps().map(|lap| lap.as_secs()).collect::<Vec<_>>(), [1, 2]); // Only the last 2 laps are kept
+/// assert_eq!(sw.lap_count(), 3); // All 3 laps are counted
+/// assert_eq!(sw.slowest_lap(), Some(Duration::from_secs(3))); // The evicted lap is still considered
+/// ```
+pub type LapStopwatch<const N: usize, C = DefaultClock> =
+	Stopwatch<C, LapBuffer<<C as Clock>::Duration, N>>;
+
+#[derive(Clone, Copy, Debug)]
+/// A ring buffer that keeps the last `N` laps recorded, and a summary of the laps before them.
+///
+/// # Example
+/// ```rust
+/// use core::time::Duration;
+/// use ticky::{LapBuffer, MockClock, Stopwatch};
+///
+/// let clock = MockClock::new(); // Create a new mock clock
+/// let mut sw = Stopwatch::with_clock(clock.clone()).with_laps(LapBuffer::<_, 4>::new()); // Create a new stopwatch that keeps its last 4 laps
+/// sw.start(); // Start the stopwatch
+/// for secs in 1..=6 {
+///     clock.advance(Duration::from_secs(secs)); // Move time forward
+///     sw.lap(); // Record a lap
+/// }
+/// assert_eq!(sw.laps.evicted().count, 2); // The first 2 laps were evicted
+/// assert_eq!(sw.laps.evicted().total, Duration::from_secs(3));
+/// assert_eq!(sw.splits().map(|split| split.as_secs()).collect::<Vec<_>>(), [6, 10, 15, 21]);
+/// ```
+pub struct LapBuffer<D, const N: usize> {
+	/// The kept laps, starting at `oldest` and wrapping around.
+	laps: [D; N],
+	/// The index of the oldest kept lap.
+	oldest: usize,
+	/// The number of laps kept.
+	len: usize,
+	/// A summary of the laps that are no longer kept.
+	evicted: EvictedLaps<D>,
+}
+
+#[derive(Ord, Eq, PartialEq, PartialOrd, Clone, Copy, Debug, Hash)]
+/// A summary of the laps a [`LapBuffer`] no longer keeps.
+pub struct EvictedLaps<D> {
+	/// The number of laps evicted.
+	pub count: usize,
+	/// The total time of all laps evicted.
+	pub total: D,
+	/// The shortest lap evicted, if any.
+	pub min: Option<D>,
+	/// The longest lap evicted, if any.
+	pub max: Option<D>,
+}
+
+impl<D: ClockDuration, const N: usize> LapBuffer<D, N> {
+	/// Creates a new, empty lap buffer.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{LapBuffer, Laps};
+	///
+	/// let laps = LapBuffer::<Duration, 8>::new(); // Create a new lap buffer keeping up to 8 laps
+	/// assert_eq!(laps.lap_count(), 0); // No laps have been recorded
+	/// ```
+	pub const fn new() -> LapBuffer<D, N> {
+		LapBuffer {
+			laps: [D::ZERO; N],
+			oldest: 0,
+			len: 0,
+			evicted: EvictedLaps {
+				count: 0,
+				total: D::ZERO,
+				min: None,
+				max: None,
+			},
+		}
+	}
+
+	/// Returns a summary of the laps that are no longer kept.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{LapBuffer, Laps};
+	///
+	/// let mut laps = LapBuffer::<Duration, 1>::new(); // Create a new lap buffer keeping only 1 lap
+	/// laps.record(Duration::from_secs(2)); // Record a lap
+	/// laps.record(Duration::from_secs(1)); // Record another lap, evicting the first
+	/// assert_eq!(laps.evicted().count, 1);
+	/// assert_eq!(laps.evicted().max, Some(Duration::from_secs(2)));
+	/// ```
+	pub const fn evicted(&self) -> &EvictedLaps<D> {
+		&self.evicted
+	}
+}
+
+impl<D: ClockDuration, const N: usize> Default for LapBuffer<D, N> {
+	fn default() -> Self {
+		LapBuffer::new()
+	}
+}
+
+impl<D: ClockDuration + Ord> EvictedLaps<D> {
+	/// Adds a lap to the summary.
+	fn evict(&mut self, lap: D) {
+		self.count = self.count.saturating_add(1);
+		self.total += lap;
+		self.min = Some(self.min.map_or(lap, |min| min.min(lap)));
+		self.max = Some(self.max.map_or(lap, |max| max.max(lap)));
+	}
+}
+
+impl<D: ClockDuration + Ord, const N: usize> Laps<D> for LapBuffer<D, N> {
+	fn record(&mut self, lap: D) {
+		if N == 0 {
+			self.evicted.evict(lap);
+		} else if self.len < N {
+			self.laps[(self.oldest + self.len) % N] = lap;
+			self.len += 1;
+		} else {
+			self.evicted.evict(self.laps[self.oldest]);
+			self.laps[self.oldest] = lap;
+			self.oldest = (self.oldest + 1) % N;
+		}
+	}
+
+	fn clear(&mut self) {
+		*self = LapBuffer::new();
+	}
+
+	fn laps(&self) -> impl Iterator<Item = D> {
+		(0..self.len).map(move |i| self.laps[(self.oldest + i) % N])
+	}
+
+	fn lap_count(&self) -> usize {
+		self.evicted.count.saturating_add(self.len)
+	}
+
+	fn fastest_lap(&self) -> Option<D> {
+		self.laps().chain(self.evicted.min).min()
+	}
+
+	fn slowest_lap(&self) -> Option<D> {
+		self.laps().chain(self.evicted.max).max()
+	}
+
+	fn nth_fastest_lap(&self, n: usize) -> Option<D> {
+		let mut laps = self.laps;
+		(n < self.len).then(|| *laps[..self.len].select_nth_unstable(n).1)
+	}
+
+	fn splits(&self) -> impl Iterator<Item = D> {
+		self.laps().scan(self.evicted.total, |split, lap| {
+			*split += lap;
+			Some(*split)
+		})
+	}
+}
+
+impl<C: Clock, L: Laps<C::Duration>> Stopwatch<C, L> {
+	/// Ends the current lap and starts a new one, returning how long the lap took.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()).with_laps(Vec::new()); // Create a new stopwatch that records laps
+	/// sw.start(); // Start the stopwatch
+	/// clock.advance(Duration::from_secs(3)); // Move time forward by 3 seconds
+	/// assert_eq!(sw.lap(), Duration::from_secs(3)); // Record the first lap
+	/// clock.advance(Duration::from_secs(2)); // Move time forward by 2 seconds
+	/// assert_eq!(sw.lap(), Duration::from_secs(2)); // Record the second lap
+	/// assert_eq!(sw.elapsed(), Duration::from_secs(5)); // Recording laps does not affect the total elapsed time
+	/// ```
+	pub fn lap(&mut self) -> C::Duration {
+		let lap = self.end_lap();
+		self.laps.record(lap);
+		lap
+	}
+
+	/// Ends the current lap and starts a new one, returning the total elapsed time at the end of the lap.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()).with_laps(Vec::new()); // Create a new stopwatch that records laps
+	/// sw.start(); // Start the stopwatch
+	/// clock.advance(Duration::from_secs(3)); // Move time forward by 3 seconds
+	/// assert_eq!(sw.split(), Duration::from_secs(3)); // Record the first lap
+	/// clock.advance(Duration::from_secs(2)); // Move time forward by 2 seconds
+	/// assert_eq!(sw.split(), Duration::from_secs(5)); // Record the second lap
+	/// assert_eq!(sw.laps, vec![Duration::from_secs(3), Duration::from_secs(2)]);
+	/// ```
+	pub fn split(&mut self) -> C::Duration {
+		self.lap();
+		self.elapsed
+	}
+
+	/// Returns the most recently recorded lap, if any.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()).with_laps(Vec::new()); // Create a new stopwatch that records laps
+	/// assert_eq!(sw.last_lap(), None); // No laps have been recorded
+	/// sw.start(); // Start the stopwatch
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// sw.lap(); // Record a lap
+	/// assert_eq!(sw.last_lap(), Some(Duration::from_secs(1)));
+	/// ```
+	pub fn last_lap(&self) -> Option<C::Duration> {
+		self.laps.last_lap()
+	}
+
+	/// Returns the recorded laps that are still kept, oldest first.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()).with_laps(Vec::new()); // Create a new stopwatch that records laps
+	/// sw.start(); // Start the stopwatch
+	/// for secs in 1..=3 {
+	///     clock.advance(Duration::from_secs(secs)); // Move time forward
+	///     sw.lap(); // Record a lap
+	/// }
+	/// assert_eq!(sw.laps().map(|lap| lap.as_secs()).collect::<Vec<_>>(), [1, 2, 3]);
+	/// assert_eq!(sw.lap_count(), 3);
+	/// ```
+	pub fn laps(&self) -> impl Iterator<Item = C::Duration> + '_ {
+		self.laps.laps()
+	}
+
+	/// Returns the split time at the end of each kept lap, oldest first.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()).with_laps(Vec::new()); // Create a new stopwatch that records laps
+	/// sw.start(); // Start the stopwatch
+	/// for secs in 1..=3 {
+	///     clock.advance(Duration::from_secs(secs)); // Move time forward
+	///     sw.lap(); // Record a lap
+	/// }
+	/// assert_eq!(sw.splits().map(|split| split.as_secs()).collect::<Vec<_>>(), [1, 3, 6]);
+	/// ```
+	pub fn splits(&self) -> impl Iterator<Item = C::Duration> + '_ {
+		self.laps.splits()
+	}
+
+	/// Returns the number of laps recorded, including any that are no longer kept.
+	///
+	/// # Example
+	/// ```rust
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let mut sw = Stopwatch::with_clock(MockClock::new()).with_laps(Vec::new()); // Create a new stopwatch that records laps
+	/// sw.lap(); // Record a lap
+	/// sw.lap(); // Record another lap
+	/// assert_eq!(sw.lap_count(), 2);
+	/// sw.reset(); // Reset the stopwatch, forgetting its laps
+	/// assert_eq!(sw.lap_count(), 0);
+	/// ```
+	pub fn lap_count(&self) -> usize {
+		self.laps.lap_count()
+	}
+
+	/// Returns the shortest lap recorded, including any that are no longer kept, if any.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()).with_laps(Vec::new()); // Create a new stopwatch that records laps
+	/// sw.start(); // Start the stopwatch
+	/// for secs in [3, 1, 2] {
+	///     clock.advance(Duration::from_secs(secs)); // Move time forward
+	///     sw.lap(); // Record a lap
+	/// }
+	/// assert_eq!(sw.fastest_lap(), Some(Duration::from_secs(1)));
+	/// ```
+	pub fn fastest_lap(&self) -> Option<C::Duration>
+	where
+		C::Duration: Ord,
+	{
+		self.laps.fastest_lap()
+	}
+
+	/// Returns the longest lap recorded, including any that are no longer kept, if any.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()).with_laps(Vec::new()); // Create a new stopwatch that records laps
+	/// sw.start(); // Start the stopwatch
+	/// for secs in [3, 1, 2] {
+	///     clock.advance(Duration::from_secs(secs)); // Move time forward
+	///     sw.lap(); // Record a lap
+	/// }
+	/// assert_eq!(sw.slowest_lap(), Some(Duration::from_secs(3)));
+	/// ```
+	pub fn slowest_lap(&self) -> Option<C::Duration>
+	where
+		C::Duration: Ord,
+	{
+		self.laps.slowest_lap()
+	}
+}
@@ -24,12 +24,53 @@
 //! println!("Elapsed time: {}ms", sw.elapsed_ms_whole());
 //! ```
 //!
+//! Elapsed times can be read in any [`Unit`] with [`Stopwatch::elapsed_in`], rounded as chosen with a [`Rounding`] mode. The results are the same whichever timekeeping backend is enabled.
+//!
+//! Calling [`Stopwatch::lap`] or [`Stopwatch::split`] ends the current lap and starts the next. Stopwatches keep no laps unless given somewhere to record them with [`Stopwatch::with_laps`], such as a `Vec` or, without allocating, a [`LapBuffer`] (see [`LapStopwatch`]). Statistics over the recorded laps are available with [`Stopwatch::lap_stats`] and [`Stopwatch::lap_percentile`].
+//!
+//! To time a single piece of work, use [`Stopwatch::measure`] (or [`Stopwatch::measure_n`] to time many runs), or wrap an expression in [`time!`], which prints how long it took.
+//!
+//! To measure async work, wrap a future with [`TimedFutureExt::timed`], which reports both the wall time and the time spent being polled. To find slow items in an iterator (or, with the `futures-core` feature, a stream), wrap it with [`TimedIteratorExt::timed`], which yields each item alongside how long it took to produce.
+//!
+//! To time a scope without calling [`Stopwatch::stop`] on every path out of it, use [`Stopwatch::scoped`], which pauses the stopwatch when the returned guard is dropped, or [`Stopwatch::scoped_with`], which hands the elapsed time to a callback.
+//!
 //! ## Features
+//! - `alloc` - Enables recording every lap of a [`Stopwatch`] in a `Vec` (see [`Laps`]) or as named [`Checkpoints`], and enables [`LatencyHistogram`] and [`SpanTimer`]. Enabled by `std`.
 //! - `derive_more` - Enables using [`derive_more`](https://crates.io/crates/derive_more) for deriving `From`, `Into`, `Mul`, `MulAssign`, `Div`, `DivAssign`, `Rem`, `Shr`, and `Shl` for `Stopwatch`.
-//! - `hifitime` - Enables using [`hifitime`](https://crates.io/crates/hifitime) for high-resolution timekeeping.
+//! - `futures-core` - Enables timing each item of a [`Stream`](https://docs.rs/futures-core/latest/futures_core/stream/trait.Stream.html); see [`TimedStreamExt`].
+//! - `hifitime` - Enables using [`hifitime`](https://crates.io/crates/hifitime) for high-resolution timekeeping. By default, durations are measured with the system's monotonic clock, and jumps in the wall clock are detected; see [`HifiClock`] and [`HifiStopwatch`].
 //! - `stdtime` - Enables using [`std::time`](https://doc.rust-lang.org/std/time/index.html) for timekeeping.
+//! - `libc` - On Linux, enables choosing which clock to measure with (such as `CLOCK_MONOTONIC_RAW` or `CLOCK_BOOTTIME`), including CPU time clocks; see [`LinuxClock`] and [`WallCpuClock`]. Also enables measuring how much time passed while the system was suspended; see [`SuspendAwareClock`].
+//!
+//! If neither `hifitime` nor `stdtime` is enabled, `stdtime` is used by default. If both are enabled, [`Stopwatch`] still uses `stdtime`, so enabling `hifitime` elsewhere in a dependency graph does not change its type. To use a particular backend regardless of features, name it with [`StdStopwatch`] or [`HifiStopwatch`]; both can be used side by side.
+//!
+//! ### `no_std`
+//! Disabling default features builds Ticky without the standard library. Time is then read from a [`TickClock`], driven by a monotonic tick counter (such as a hardware timer) that you supply:
+//! ```rust
+//! use ticky::{Stopwatch, TickClock};
+//!
+//! fn read_ticks() -> u64 {
+//!     0 // Read a hardware counter here
+//! }
+//!
+//! let mut sw = Stopwatch::with_clock(TickClock::new(read_ticks, 1_000_000)); // Create a new stopwatch using a 1 MHz tick counter
+//! sw.start();
+//! // Do something …
+//! sw.stop();
+//! ```
+//!
+//! ## Clocks
+//! A [`Stopwatch`] reads time from a [`Clock`]. The clock chosen by the enabled features is [`DefaultClock`], but any type implementing [`Clock`] can be used with [`Stopwatch::with_clock`].
 //!
-//! Either `hifitime` or `stdtime` must be enabled. If neither is enabled, `stdtime` is used by default. If both are enabled, `hifitime` is used.
+//! Clocks that can fail to be read (such as the `hifitime` clock, which depends on the system time) panic when they do. Each method that reads the clock has a `try_` counterpart that returns a [`TickyError`] instead.
+//!
+//! To count down rather than up, use a [`Timer`], which reads time from the same clocks as a [`Stopwatch`]. To give an operation a time budget and share it out among its steps, use a [`Deadline`]. To run work periodically without drift, use a [`Ticker`].
+//!
+//! To share one stopwatch between threads without a lock, use an [`AtomicStopwatch`].
+//!
+//! To see where time goes within nested pieces of work, use a [`SpanTimer`], which builds a call tree of named spans.
+//!
+//! For deterministic tests, a [`MockClock`] (available with the `std` feature) only moves forward when told to.
 //!
 //! ## Installation
 //! Run `cargo add ticky` to add Ticky to your `Cargo.toml` file.
@@ -49,541 +90,551 @@
 #![no_std]
 #![warn(missing_docs)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
-cfg_if::cfg_if! {
-	if #[cfg(any(feature = "hifitime"))] {
-		use hifitime::{Duration, Epoch, TimeUnits, Unit};
-
-		#[cfg(feature = "derive_more")]
-		use derive_more::{Div, DivAssign, From, Into, Mul, MulAssign, Rem, Shl, Shr};
+#[cfg(target_has_atomic = "64")]
+mod atomic;
+#[cfg(target_has_atomic = "64")]
+pub use atomic::*;
+#[cfg(feature = "alloc")]
+mod checkpoints;
+#[cfg(feature = "alloc")]
+pub use checkpoints::*;
+mod clock;
+pub use clock::*;
+mod deadline;
+pub use deadline::*;
+mod error;
+pub use error::*;
+mod future;
+pub use future::*;
+#[cfg(feature = "alloc")]
+mod histogram;
+#[cfg(feature = "alloc")]
+pub use histogram::*;
+mod iter;
+pub use iter::*;
+mod laps;
+pub use laps::*;
+#[cfg(all(feature = "libc", any(target_os = "linux", target_os = "android")))]
+mod linux;
+#[cfg(all(feature = "libc", any(target_os = "linux", target_os = "android")))]
+pub use linux::*;
+mod measure;
+#[cfg(feature = "std")]
+mod mock;
+#[cfg(feature = "std")]
+pub use mock::*;
+mod scope;
+pub use scope::*;
+#[cfg(feature = "alloc")]
+mod spans;
+#[cfg(feature = "alloc")]
+pub use spans::*;
+mod stats;
+pub use stats::*;
+mod ticker;
+pub use ticker::*;
+#[cfg(feature = "futures-core")]
+mod stream;
+#[cfg(feature = "futures-core")]
+pub use stream::*;
+mod timer;
+pub use timer::*;
+mod units;
+pub use units::*;
+
+#[cfg(feature = "derive_more")]
+use derive_more::{Div, DivAssign, From, Into, Mul, MulAssign, Rem, Shl, Shr};
+
+#[cfg_attr(
+	feature = "derive_more",
+	derive(From, Into, Mul, MulAssign, Div, DivAssign, Rem, Shr, Shl,)
+)]
+#[derive(Ord, Eq, PartialEq, PartialOrd, Clone, Copy, Debug, Hash)]
+/// A simple stopwatch implementation.
+/// ## Usage
+/// ```rust
+/// use ticky::Stopwatch;
+/// let mut sw = Stopwatch::start_new();
+/// // Do something …
+/// sw.stop();
+/// println!("Elapsed time: {}ms", sw.elapsed_ms_whole());
+/// ```
+pub struct Stopwatch<C: Clock = DefaultClock, L = ()> {
+	/// The total elapsed time.
+	pub elapsed: C::Duration,
+	/// The time at which the stopwatch was last started, if it has been started.
+	pub timer: Option<C::Instant>,
+	/// Whether the stopwatch is currently running.
+	pub is_running: bool,
+	/// The clock the stopwatch reads time from.
+	pub clock: C,
+	/// The elapsed time since the last lap was recorded.
+	pub lap_elapsed: C::Duration,
+	/// The laps recorded so far.
+	pub laps: L,
+}
 
-		#[cfg_attr(
-			feature = "derive_more",
-			derive(From, Into, Mul, MulAssign, Div, DivAssign, Rem, Shr, Shl,)
-		)]
-		#[derive(Ord, Eq, PartialEq, PartialOrd, Clone, Copy, Debug, Hash)]
-		/// A simple stopwatch implementation.
-		/// ## Usage
-		/// ```rust
-		/// use ticky::Stopwatch;
-		/// let mut sw = Stopwatch::start_new();
-		/// // Do something …
-		/// sw.stop();
-		/// println!("Elapsed time: {}ms", sw.elapsed_ms_whole());
-		/// ```
-		pub struct Stopwatch {
-			/// The total elapsed time.
-			pub elapsed: Duration,
-			/// The time at which the stopwatch was last started.
-			pub timer: Duration,
-			/// Whether the stopwatch is currently running.
-			pub is_running: bool,
-		}
+#[cfg(feature = "stdtime")]
+/// A stopwatch that reads time from [`std::time`](https://doc.rust-lang.org/std/time/index.html), whichever other features are enabled.
+///
+/// # Example
+/// ```rust
+/// use ticky::StdStopwatch;
+///
+/// let mut sw = StdStopwatch::default(); // Create a new stopwatch
+/// sw.start(); // Start the stopwatch
+/// std::thread::sleep(std::time::Duration::from_millis(10)); // Wait for 10 milliseconds
+/// sw.stop(); // Stop the stopwatch
+/// let elapsed: std::time::Duration = sw.elapsed(); // The elapsed time is a standard library duration
+/// assert!(elapsed.as_millis() >= 10); // At least 10 milliseconds have passed
+/// ```
+pub type StdStopwatch = Stopwatch<StdClock>;
+
+#[cfg(all(feature = "hifitime", feature = "std"))]
+/// A stopwatch that reads time from [`hifitime`](https://crates.io/crates/hifitime), whichever other features are enabled.
+///
+/// # Example
+/// ```rust
+/// use ticky::HifiStopwatch;
+///
+/// let mut sw = HifiStopwatch::default(); // Create a new stopwatch
+/// sw.start(); // Start the stopwatch
+/// std::thread::sleep(std::time::Duration::from_millis(10)); // Wait for 10 milliseconds
+/// sw.stop(); // Stop the stopwatch
+/// let elapsed: hifitime::Duration = sw.elapsed(); // The elapsed time is a hifitime duration
+/// assert!(elapsed.to_seconds() >= 0.01); // At least 10 milliseconds have passed
+/// ```
+pub type HifiStopwatch = Stopwatch<HifiClock>;
+
+impl<C: Clock<Duration = core::time::Duration>> From<Stopwatch<C>> for core::time::Duration {
+	fn from(sw: Stopwatch<C>) -> Self {
+		sw.elapsed()
+	}
+}
 
-		impl Display for Stopwatch {
-			fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-				write!(f, "{}", self.elapsed())
-			}
+impl<C: Clock<Duration = core::time::Duration> + Default> From<core::time::Duration>
+	for Stopwatch<C>
+{
+	fn from(dur: core::time::Duration) -> Self {
+		Self {
+			elapsed: dur,
+			..Stopwatch::default()
 		}
+	}
+}
 
-		impl From<Duration> for Stopwatch {
-			fn from(dur: Duration) -> Self {
-				Self {
-					elapsed: dur,
-					timer: Epoch::now().unwrap().to_duration(),
-					is_running: false,
-				}
-			}
-		}
+#[cfg(feature = "hifitime")]
+impl<C: Clock<Duration = hifitime::Duration>> From<Stopwatch<C>> for hifitime::Duration {
+	fn from(sw: Stopwatch<C>) -> Self {
+		sw.elapsed()
+	}
+}
 
-		impl Default for Stopwatch {
-			fn default() -> Self {
-				Self {
-					elapsed: Duration::ZERO,
-					timer: Epoch::now().unwrap().to_duration(),
-					is_running: false,
-				}
-			}
+#[cfg(feature = "hifitime")]
+impl<C: Clock<Duration = hifitime::Duration> + Default> From<hifitime::Duration> for Stopwatch<C> {
+	fn from(dur: hifitime::Duration) -> Self {
+		Self {
+			elapsed: dur,
+			..Stopwatch::default()
 		}
+	}
+}
 
-		impl Stopwatch {
-			/// Starts (or resumes) the stopwatch.
-			///
-			/// # Example
-			/// ```rust
-			/// use ticky::Stopwatch;
-			///
-			/// let mut sw = Stopwatch::new(); // Create a new stopwatch
-			/// sw.start(); // Start the stopwatch
-			/// std::thread::sleep(std::time::Duration::from_millis(1_000)); // Wait for 1 second
-			/// sw.stop(); // Stop the stopwatch (pausing it)
-			/// assert!(sw.elapsed_ms_whole().abs_diff(1_000) < 100); // Allow for some error (± 100 milliseconds)
-			/// sw.start(); // Start the stopwatch again (resuming it)
-			/// std::thread::sleep(std::time::Duration::from_millis(1_000)); // Wait for 1 second
-			/// sw.stop(); // Stop the stopwatch
-			/// assert!(sw.elapsed_ms_whole().abs_diff(2_000) < 100); // Allow for some error (± 100 milliseconds)
-			/// ```
-			pub fn start(&mut self) {
-				self.timer = Epoch::now().unwrap().to_duration();
-				self.is_running = true;
-			}
-
-			/// Stops (or pauses) the stopwatch.
-			///
-			/// # Example
-			/// ```rust
-			/// use ticky::Stopwatch;
-			///
-			/// let mut sw = Stopwatch::new(); // Create a new stopwatch
-			/// sw.start(); // Start the stopwatch
-			/// std::thread::sleep(std::time::Duration::from_millis(1_000)); // Wait for 1 second
-			/// sw.stop(); // Stop the stopwatch (pausing it)
-			/// assert!(sw.elapsed_ms_whole().abs_diff(1_000) < 100); // Allow for some error (± 100 milliseconds)
-			/// sw.start(); // Start the stopwatch again (resuming it)
-			/// std::thread::sleep(std::time::Duration::from_millis(1_000)); // Wait for 1 second
-			/// sw.stop(); // Stop the stopwatch
-			/// assert!(sw.elapsed_ms_whole().abs_diff(2_000) < 100); // Allow for some error (± 100 milliseconds)
-			/// ```
-			pub fn stop(&mut self) {
-				self.elapsed += (Epoch::now().unwrap().to_duration() - self.timer).abs();
-				self.is_running = false;
-			}
-
-			/// Returns the total elapsed time.
-			///
-			/// # Example
-			/// ```rust
-			/// use ticky::Stopwatch;
-			///
-			/// let mut sw = Stopwatch::new(); // Create a new stopwatch
-			/// sw.start(); // Start the stopwatch
-			/// std::thread::sleep(std::time::Duration::from_millis(1_000)); // Wait for 1 second
-			/// sw.stop(); // Stop the stopwatch
-			/// assert!((sw.elapsed().to_unit(hifitime::prelude::Unit::Millisecond) - 1_000.0).abs() < 100.0); // Allow for some error (± 100 milliseconds)
-			/// ```
-			pub fn elapsed(&self) -> Duration {
-				match self.is_running {
-					true =>  self.elapsed + (Epoch::now().unwrap().to_duration() - self.timer).abs(),
-					false => self.elapsed,
-				}
-			}
-
-			/// Returns the total elapsed time in fractional milliseconds.
-			///
-			/// # Example
-			/// ```rust
-			/// use ticky::Stopwatch;
-			///
-			/// let mut sw = Stopwatch::new(); // Create a new stopwatch
-			/// sw.start(); // Start the stopwatch
-			/// std::thread::sleep(std::time::Duration::from_millis(1_000)); // Wait for 1 second
-			/// sw.stop(); // Stop the stopwatch
-			/// assert!((sw.elapsed_ms() - 1_000.0).abs() < 100.0); // Allow for some error (± 100 milliseconds)
-			/// ```
-			pub fn elapsed_ms(&mut self) -> f64 {
-				self.elapsed().to_unit(Unit::Millisecond)
-			}
-
-			/// Returns the total elapsed time in whole milliseconds.
-			///
-			/// # Example
-			/// ```rust
-			/// use ticky::Stopwatch;
-			///
-			/// let mut sw = Stopwatch::new(); // Create a new stopwatch
-			/// sw.start(); // Start the stopwatch
-			/// std::thread::sleep(std::time::Duration::from_millis(1_000)); // Wait for 1 second
-			/// sw.stop(); // Stop the stopwatch
-			/// assert!(sw.elapsed_ms_whole().abs_diff(1_000) < 100); // Allow for some error (± 100 milliseconds)
-			/// ```
-			pub fn elapsed_ms_whole(&mut self) -> u128 {
-				self.elapsed().round(1.milliseconds()).to_unit(Unit::Millisecond).round() as u128
-			}
+impl<C: Clock + Default, L: Default> Default for Stopwatch<C, L> {
+	fn default() -> Self {
+		Stopwatch::with_clock(C::default()).with_laps(L::default())
+	}
+}
 
-			/// Returns the total elapsed time in fractional microseconds.
-			///
-			/// # Example
-			/// ```rust
-			/// use ticky::Stopwatch;
-			///
-			/// let mut sw = Stopwatch::new(); // Create a new stopwatch
-			/// sw.start(); // Start the stopwatch
-			/// std::thread::sleep(std::time::Duration::from_micros(1_000_000)); // Wait for 1 second
-			/// sw.stop(); // Stop the stopwatch
-			/// assert!((sw.elapsed_us() - 1_000_000.0).abs() < 10_000.0); // Allow for some error (± 10,000 microseconds)
-			/// ```
-			pub fn elapsed_us(&mut self) -> f64 {
-				self.elapsed().to_unit(Unit::Microsecond)
-			}
+#[cfg(any(feature = "stdtime", all(feature = "hifitime", feature = "std")))]
+impl Stopwatch {
+	/// Creates a new stopwatch.
+	///
+	/// The clock is not read until the stopwatch is started, so this can be used to initialise a `static`.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::Stopwatch;
+	///
+	/// let mut sw = Stopwatch::new(); // Create a new stopwatch
+	/// assert_eq!(sw.is_running(), false); // The stopwatch is not running
+	/// assert_eq!(sw.elapsed(), Duration::ZERO); // No time is counted until the stopwatch is started
+	///
+	/// static GLOBAL_SW: std::sync::Mutex<Stopwatch> = std::sync::Mutex::new(Stopwatch::new()); // Create a new stopwatch in a `static`
+	/// GLOBAL_SW.lock().unwrap().start(); // Start the stopwatch
+	/// assert_eq!(GLOBAL_SW.lock().unwrap().is_running(), true); // The stopwatch is running
+	/// ```
+	pub const fn new() -> Stopwatch {
+		Stopwatch::with_clock(DEFAULT_CLOCK)
+	}
 
-			/// Returns the total elapsed time in whole microseconds.
-			///
-			/// # Example
-			/// ```rust
-			/// use ticky::Stopwatch;
-			///
-			/// let mut sw = Stopwatch::new(); // Create a new stopwatch
-			/// sw.start(); // Start the stopwatch
-			/// std::thread::sleep(std::time::Duration::from_micros(1_000_000)); // Wait for 1 second
-			/// sw.stop(); // Stop the stopwatch
-			/// assert!(sw.elapsed_us_whole().abs_diff(1_000_000) < 10_000); // Allow for some error (± 10,000 microseconds)
-			/// ```
-			pub fn elapsed_us_whole(&mut self) -> u128 {
-				self.elapsed().round(1.microseconds()).to_unit(Unit::Microsecond).round() as u128
-			}
+	/// Creates a new stopwatch and starts it.
+	///
+	/// # Example
+	/// ```rust
+	/// use ticky::Stopwatch;
+	///
+	/// let mut sw = Stopwatch::start_new(); // Create a new stopwatch, and start it
+	/// assert_eq!(sw.is_running(), true); // The stopwatch is running
+	/// sw.stop(); // Stop the stopwatch
+	/// assert_eq!(sw.elapsed(), sw.elapsed()); // The elapsed time does not change while the stopwatch is stopped
+	/// ```
+	pub fn start_new() -> Stopwatch {
+		let mut sw = Stopwatch::new();
+		sw.start();
+		sw
+	}
 
-			/// Returns the total elapsed time in fractional nanoseconds.
-			///
-			/// # Example
-			/// ```rust
-			/// use ticky::Stopwatch;
-			///
-			/// let mut sw = Stopwatch::new(); // Create a new stopwatch
-			/// sw.start(); // Start the stopwatch
-			/// std::thread::sleep(std::time::Duration::from_nanos(1_000_000_000)); // Wait for 1 second
-			/// sw.stop(); // Stop the stopwatch
-			/// assert!((sw.elapsed_ns() - 1_000_000_000.0).abs() < 100_000_000.0); // Allow for some error (± 100,000,000 nanoseconds)
-			/// ```
-			pub fn elapsed_ns(&mut self) -> f64 {
-				self.elapsed().to_unit(Unit::Nanosecond)
-			}
+	/// Creates a new stopwatch and starts it, or returns an error if the clock could not be read.
+	///
+	/// # Example
+	/// ```rust
+	/// use ticky::Stopwatch;
+	///
+	/// let sw = Stopwatch::try_start_new().expect("the clock could not be read"); // Create a new stopwatch, and start it
+	/// assert_eq!(sw.is_running, true); // The stopwatch is running
+	/// ```
+	pub fn try_start_new() -> Result<Stopwatch, TickyError> {
+		let mut sw = Stopwatch::new();
+		sw.try_start()?;
+		Ok(sw)
+	}
+}
 
-			/// Returns the total elapsed time in whole nanoseconds.
-			///
-			/// # Example
-			/// ```rust
-			/// use ticky::Stopwatch;
-			///
-			/// let mut sw = Stopwatch::new(); // Create a new stopwatch
-			/// sw.start(); // Start the stopwatch
-			/// std::thread::sleep(std::time::Duration::from_nanos(1_000_000_000)); // Wait for 1 second
-			/// sw.stop(); // Stop the stopwatch
-			/// assert!(sw.elapsed_ns_whole().abs_diff(1_000_000_000) < 100_000_000); // Allow for some error (± 100,000,000 nanoseconds)
-			/// ```
-			pub fn elapsed_ns_whole(&mut self) -> u128 {
-				self.elapsed().total_nanoseconds().try_into().unwrap()
-			}
+#[cfg(all(feature = "hifitime", feature = "std"))]
+impl HifiStopwatch {
+	/// Creates a new stopwatch that reads time from [`hifitime`](https://crates.io/crates/hifitime), measuring with the system's monotonic clock.
+	///
+	/// # Example
+	/// ```rust
+	/// use ticky::Stopwatch;
+	///
+	/// let mut sw = Stopwatch::new_hifi(); // Create a new stopwatch
+	/// sw.start(); // Start the stopwatch
+	/// std::thread::sleep(std::time::Duration::from_millis(10)); // Wait for 10 milliseconds
+	/// sw.stop(); // Stop the stopwatch
+	/// assert!(sw.elapsed_ms_whole() >= 10); // At least 10 milliseconds have passed
+	/// ```
+	pub const fn new_hifi() -> HifiStopwatch {
+		Stopwatch::with_clock(HifiClock::monotonic())
+	}
 
-			/// Returns the total elapsed time in fractional seconds.
-			///
-			/// # Example
-			/// ```rust
-			/// use ticky::Stopwatch;
-			///
-			/// let mut sw = Stopwatch::new(); // Create a new stopwatch
-			/// sw.start(); // Start the stopwatch
-			/// std::thread::sleep(std::time::Duration::from_millis(1_500)); // Wait for 1.5 seconds
-			/// sw.stop(); // Stop the stopwatch
-			/// assert!((sw.elapsed_s() - 1.5).abs() < 0.01); // Allow for some error (± 0.01 seconds)
-			/// ```
-			pub fn elapsed_s(&mut self) -> f64 {
-				self.elapsed().to_unit(Unit::Second)
-			}
+	/// Creates a new stopwatch that reads time from [`hifitime`](https://crates.io/crates/hifitime), and starts it.
+	///
+	/// # Example
+	/// ```rust
+	/// use ticky::Stopwatch;
+	///
+	/// let mut sw = Stopwatch::start_new_hifi(); // Create a new stopwatch, and start it
+	/// std::thread::sleep(std::time::Duration::from_millis(10)); // Wait for 10 milliseconds
+	/// sw.stop(); // Stop the stopwatch
+	/// assert!(sw.elapsed_ms_whole() >= 10); // At least 10 milliseconds have passed
+	/// ```
+	pub fn start_new_hifi() -> HifiStopwatch {
+		let mut sw = Stopwatch::new_hifi();
+		sw.start();
+		sw
+	}
+}
 
-			/// Returns the total elapsed time in whole seconds.
-			///
-			/// # Example
-			/// ```rust
-			/// use ticky::Stopwatch;
-			///
-			/// let mut sw = Stopwatch::new(); // Create a new stopwatch
-			/// sw.start(); // Start the stopwatch
-			/// std::thread::sleep(std::time::Duration::from_millis(1_500)); // Wait for 1.5 seconds
-			/// sw.stop(); // Stop the stopwatch
-			/// assert_eq!(sw.elapsed_s_whole(), 2);
-			/// ```
-			pub fn elapsed_s_whole(&self) -> u64 {
-				self.elapsed().round(1.seconds()).to_seconds().round() as u64
-			}
+impl<C: Clock> Stopwatch<C> {
+	/// Creates a new stopwatch that reads time from the given clock.
+	///
+	/// The clock is not read until the stopwatch is started, so this can be used to initialise a `static`.
+	///
+	/// # Example
+	/// ```rust
+	/// use ticky::{DefaultClock, Stopwatch, TickClock};
+	///
+	/// let mut sw = Stopwatch::with_clock(DefaultClock::default()); // Create a new stopwatch using the default clock
+	/// assert_eq!(sw.is_running(), false); // The stopwatch is not running
+	///
+	/// fn read_ticks() -> u64 {
+	///     0 // Read a hardware counter here
+	/// }
+	///
+	/// static TICK_SW: std::sync::Mutex<Stopwatch<TickClock>> =
+	///     std::sync::Mutex::new(Stopwatch::with_clock(TickClock::new(read_ticks, 1_000))); // Create a new stopwatch in a `static`
+	/// assert_eq!(TICK_SW.lock().unwrap().timer, None); // The clock has not been read yet
+	/// ```
+	pub const fn with_clock(clock: C) -> Stopwatch<C> {
+		Stopwatch {
+			elapsed: C::Duration::ZERO,
+			timer: None,
+			is_running: false,
+			clock,
+			lap_elapsed: C::Duration::ZERO,
+			laps: (),
 		}
+	}
+}
 
-	} else if #[cfg(feature = "stdtime")] {
-		use core::time::Duration;
-
-		#[cfg(feature = "std")]
-		use std::time::Instant;
-
-		#[cfg(feature = "derive_more")]
-		use derive_more::{Div, DivAssign, From, Into, Mul, MulAssign, Rem, Shl, Shr};
-
-		#[cfg_attr(
-			feature = "derive_more",
-			derive(From, Into, Mul, MulAssign, Div, DivAssign, Rem, Shr, Shl,)
-		)]
-		#[derive(Ord, Eq, PartialEq, PartialOrd, Clone, Copy, Debug, Hash)]
-		/// A simple stopwatch implementation.
-		/// ## Usage
-		/// ```rust
-		/// use ticky::Stopwatch;
-		/// let mut sw = Stopwatch::start_new();
-		/// // Do something …
-		/// sw.stop();
-		/// println!("Elapsed time: {}ms", sw.elapsed_ms_whole());
-		/// ```
-		pub struct Stopwatch {
-			/// The total elapsed time.
-			pub elapsed: Duration,
-			/// The time at which the stopwatch was last started.
-			pub timer: Instant,
-			/// Whether the stopwatch is currently running.
-			pub is_running: bool,
+impl<C: Clock, L> Stopwatch<C, L> {
+	/// Replaces where the stopwatch records its laps, discarding any laps already recorded.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()).with_laps(Vec::new()); // Create a new stopwatch that records laps in a `Vec`
+	/// sw.start(); // Start the stopwatch
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// sw.lap(); // Record a lap
+	/// assert_eq!(sw.laps, vec![Duration::from_secs(1)]);
+	/// ```
+	pub fn with_laps<M>(self, laps: M) -> Stopwatch<C, M> {
+		Stopwatch {
+			elapsed: self.elapsed,
+			timer: self.timer,
+			is_running: self.is_running,
+			clock: self.clock,
+			lap_elapsed: self.lap_elapsed,
+			laps,
 		}
+	}
 
-		impl Display for Stopwatch {
-			fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-				let elapsed_ms = self.clone().elapsed_ms_whole();
-				write!(f, "{elapsed_ms}ms")
-			}
+	/// Ends the current lap and starts a new one, returning how long the lap took without recording it.
+	pub(crate) fn end_lap(&mut self) -> C::Duration {
+		if let (true, Some(timer)) = (self.is_running, self.timer) {
+			let now = self.clock.now();
+			let lap = self.clock.duration_between(timer, now);
+			self.elapsed += lap;
+			self.lap_elapsed += lap;
+			self.timer = Some(now);
 		}
+		core::mem::replace(&mut self.lap_elapsed, C::Duration::ZERO)
+	}
 
-		impl From<Duration> for Stopwatch {
-			fn from(dur: Duration) -> Self {
-				Self {
-					elapsed: dur,
-					timer: Instant::now(),
-					is_running: false,
-				}
-			}
-		}
+	/// Starts (or resumes) the stopwatch.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
+	/// sw.start(); // Start the stopwatch
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// sw.stop(); // Stop the stopwatch (pausing it)
+	/// assert_eq!(sw.elapsed(), Duration::from_secs(1));
+	/// clock.advance(Duration::from_secs(1)); // Time passing while stopped is not counted
+	/// sw.start(); // Start the stopwatch again (resuming it)
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// sw.stop(); // Stop the stopwatch
+	/// assert_eq!(sw.elapsed(), Duration::from_secs(2));
+	/// ```
+	pub fn start(&mut self) {
+		self.timer = Some(self.clock.now());
+		self.is_running = true;
+	}
 
-		impl Default for Stopwatch {
-			fn default() -> Self {
-				Self {
-					elapsed: Duration::new(0, 0),
-					timer: Instant::now(),
-					is_running: false,
-				}
-			}
+	/// Stops (or pauses) the stopwatch.
+	///
+	/// Stopping a stopwatch that is not running has no effect.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
+	/// sw.start(); // Start the stopwatch
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// sw.stop(); // Stop the stopwatch (pausing it)
+	/// assert_eq!(sw.elapsed(), Duration::from_secs(1));
+	/// clock.advance(Duration::from_secs(1)); // Time passing while stopped is not counted
+	/// sw.start(); // Start the stopwatch again (resuming it)
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// sw.stop(); // Stop the stopwatch
+	/// assert_eq!(sw.elapsed(), Duration::from_secs(2));
+	/// sw.stop(); // Stopping again has no effect
+	/// assert_eq!(sw.elapsed(), Duration::from_secs(2));
+	/// ```
+	pub fn stop(&mut self) {
+		if !self.is_running {
+			return;
 		}
-
-		impl Stopwatch {
-			/// Starts (or resumes) the stopwatch.
-			///
-			/// # Example
-			/// ```rust
-			/// use ticky::Stopwatch;
-			///
-			/// let mut sw = Stopwatch::new(); // Create a new stopwatch
-			/// sw.start(); // Start the stopwatch
-			/// std::thread::sleep(std::time::Duration::from_millis(1_000)); // Wait for 1 second
-			/// sw.stop(); // Stop the stopwatch (pausing it)
-			/// assert!(sw.elapsed_ms_whole().abs_diff(1_000) < 100); // Allow for some error (± 100 milliseconds)
-			/// sw.start(); // Start the stopwatch again (resuming it)
-			/// std::thread::sleep(std::time::Duration::from_millis(1_000)); // Wait for 1 second
-			/// sw.stop(); // Stop the stopwatch
-			/// assert!(sw.elapsed_ms_whole().abs_diff(2_000) < 100); // Allow for some error (± 100 milliseconds)
-			/// ```
-			pub fn start(&mut self) {
-				self.timer = Instant::now();
-				self.is_running = true;
-			}
-
-			/// Stops (or pauses) the stopwatch.
-			///
-			/// # Example
-			/// ```rust
-			/// use ticky::Stopwatch;
-			///
-			/// let mut sw = Stopwatch::new(); // Create a new stopwatch
-			/// sw.start(); // Start the stopwatch
-			/// std::thread::sleep(std::time::Duration::from_millis(1_000)); // Wait for 1 second
-			/// sw.stop(); // Stop the stopwatch (pausing it)
-			/// assert!(sw.elapsed_ms_whole().abs_diff(1_000) < 100); // Allow for some error (± 100 milliseconds)
-			/// sw.start(); // Start the stopwatch again (resuming it)
-			/// std::thread::sleep(std::time::Duration::from_millis(1_000)); // Wait for 1 second
-			/// sw.stop(); // Stop the stopwatch
-			/// assert!(sw.elapsed_ms_whole().abs_diff(2_000) < 100); // Allow for some error (± 100 milliseconds)
-			/// ```
-			pub fn stop(&mut self) {
-				self.elapsed += self.timer.elapsed();
-				self.is_running = false;
-			}
-
-			/// Returns the total elapsed time.
-			///
-			/// # Example
-			/// ```rust
-			/// use ticky::Stopwatch;
-			///
-			/// let mut sw = Stopwatch::new(); // Create a new stopwatch
-			/// sw.start(); // Start the stopwatch
-			/// std::thread::sleep(std::time::Duration::from_millis(1_000)); // Wait for 1 second
-			/// sw.stop(); // Stop the stopwatch
-			/// assert!(sw.elapsed().as_millis().abs_diff(1_000) < 100); // Allow for some error (± 100 milliseconds)
-			/// ```
-			pub fn elapsed(&self) -> Duration {
-				match self.is_running {
-					true => self.elapsed + self.timer.elapsed(),
-					false => self.elapsed,
-				}
-			}
-
-			/// Returns the total elapsed time in milliseconds.
-			///
-			/// # Example
-			/// ```rust
-			/// use ticky::Stopwatch;
-			///
-			/// let mut sw = Stopwatch::new(); // Create a new stopwatch
-			/// sw.start(); // Start the stopwatch
-			/// std::thread::sleep(std::time::Duration::from_millis(1_000)); // Wait for 1 second
-			/// sw.stop(); // Stop the stopwatch
-			/// assert!(sw.elapsed_ms_whole().abs_diff(1_000) < 100); // Allow for some error (± 100 milliseconds)
-			/// ```
-			pub fn elapsed_ms_whole(&mut self) -> u128 {
-				self.elapsed().as_millis()
-			}
-
-			/// Returns the total elapsed time in microseconds.
-			///
-			/// # Example
-			/// ```rust
-			/// use ticky::Stopwatch;
-			///
-			/// let mut sw = Stopwatch::new(); // Create a new stopwatch
-			/// sw.start(); // Start the stopwatch
-			/// std::thread::sleep(std::time::Duration::from_micros(1_000_000)); // Wait for 1 second
-			/// sw.stop(); // Stop the stopwatch
-			/// assert!(sw.elapsed_us_whole().abs_diff(1_000_000) < 10_000); // Allow for some error (± 10,000 microseconds)
-			/// ```
-			pub fn elapsed_us_whole(&mut self) -> u128 {
-				self.elapsed().as_micros()
-			}
-
-			/// Returns the total elapsed time in nanoseconds.
-			///
-			/// # Example
-			/// ```rust
-			/// use ticky::Stopwatch;
-			///
-			/// let mut sw = Stopwatch::new(); // Create a new stopwatch
-			/// sw.start(); // Start the stopwatch
-			/// std::thread::sleep(std::time::Duration::from_nanos(1_000_000_000)); // Wait for 1 second
-			/// sw.stop(); // Stop the stopwatch
-			/// assert!(sw.elapsed_ns_whole().abs_diff(1_000_000_000) < 100_000_000); // Allow for some error (± 100,000,000 nanoseconds)
-			/// ```
-			pub fn elapsed_ns_whole(&mut self) -> u128 {
-				self.elapsed().as_nanos()
-			}
-
-			/// Returns the total elapsed time in fractional seconds.
-			///
-			/// # Example
-			/// ```rust
-			/// use ticky::Stopwatch;
-			///
-			/// let mut sw = Stopwatch::new(); // Create a new stopwatch
-			/// sw.start(); // Start the stopwatch
-			/// std::thread::sleep(std::time::Duration::from_millis(1_500)); // Wait for 1.5 seconds
-			/// sw.stop(); // Stop the stopwatch
-			/// assert!((sw.elapsed_s() - 1.5).abs() < 0.01); // Allow for some error (± 0.01 seconds)
-			/// ```
-			pub fn elapsed_s(&mut self) -> f64 {
-				self.elapsed().as_secs_f64()
-			}
-
-			/// Returns the total elapsed time in whole seconds.
-			///
-			/// # Example
-			/// ```rust
-			/// use ticky::Stopwatch;
-			///
-			/// let mut sw = Stopwatch::new(); // Create a new stopwatch
-			/// sw.start(); // Start the stopwatch
-			/// std::thread::sleep(std::time::Duration::from_millis(1_500)); // Wait for 1.5 seconds
-			/// sw.stop(); // Stop the stopwatch
-			/// assert_eq!(sw.elapsed_s_whole(), 1);
-			/// ```
-			pub fn elapsed_s_whole(&self) -> u64 {
-				self.elapsed().as_secs()
-			}
+		if let Some(timer) = self.timer {
+			let lap = self.clock.duration_between(timer, self.clock.now());
+			self.elapsed += lap;
+			self.lap_elapsed += lap;
 		}
-	} else {
-		compile_error!("You must enable either the `stdtime` or `hifitime` feature.");
+		self.is_running = false;
 	}
-}
 
-use core::fmt::{Display, Formatter};
+	/// Starts (or resumes) the stopwatch, or returns an error if the clock could not be read.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
+	/// sw.try_start()?; // Start the stopwatch
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// sw.try_stop()?; // Stop the stopwatch
+	/// assert_eq!(sw.try_elapsed()?, Duration::from_secs(1));
+	/// # Ok::<(), ticky::TickyError>(())
+	/// ```
+	pub fn try_start(&mut self) -> Result<(), TickyError> {
+		self.timer = Some(self.clock.try_now()?);
+		self.is_running = true;
+		Ok(())
+	}
 
-impl From<Stopwatch> for Duration {
-	fn from(sw: Stopwatch) -> Self {
-		sw.elapsed()
+	/// Stops (or pauses) the stopwatch, or returns an error if the clock could not be read, the time could not be measured reliably, or the elapsed time overflowed.
+	///
+	/// Stopping a stopwatch that is not running has no effect. If an error is returned, the stopwatch is left unchanged; if the clock jumped, [`stop`](Stopwatch::stop) can still be used to accept the measurement.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch, TickyError};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
+	/// sw.elapsed = Duration::MAX; // Pretend the stopwatch has been running for a very long time
+	/// sw.start(); // Start the stopwatch
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// assert_eq!(sw.try_stop(), Err(TickyError::Overflow)); // The elapsed time cannot be represented
+	/// assert_eq!(sw.is_running(), true); // The stopwatch is still running
+	/// sw.elapsed = Duration::ZERO; // Make the elapsed time representable again
+	/// assert_eq!(sw.try_stop(), Ok(())); // Stop the stopwatch
+	/// assert_eq!(sw.try_stop(), Ok(())); // Stopping again has no effect
+	/// assert_eq!(sw.elapsed(), Duration::from_secs(1));
+	/// ```
+	pub fn try_stop(&mut self) -> Result<(), TickyError> {
+		if !self.is_running {
+			return Ok(());
+		}
+		if let Some(timer) = self.timer {
+			let now = self.clock.try_now()?;
+			let lap = self.clock.try_duration_between(timer, now)?;
+			let elapsed = self.elapsed.checked_add(lap);
+			let lap_elapsed = self.lap_elapsed.checked_add(lap);
+			(self.elapsed, self.lap_elapsed) =
+				elapsed.zip(lap_elapsed).ok_or(TickyError::Overflow)?;
+		}
+		self.is_running = false;
+		Ok(())
 	}
-}
 
-impl Stopwatch {
-	/// Creates a new stopwatch.
+	/// Returns the total elapsed time.
 	///
 	/// # Example
 	/// ```rust
-	/// use ticky::Stopwatch;
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
 	///
-	/// let mut sw = Stopwatch::new(); // Create a new stopwatch
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
 	/// sw.start(); // Start the stopwatch
-	/// std::thread::sleep(std::time::Duration::from_millis(1_000)); // Wait for 1 second
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// assert_eq!(sw.elapsed(), Duration::from_secs(1)); // The elapsed time can be read while the stopwatch is running
 	/// sw.stop(); // Stop the stopwatch
-	/// assert!(sw.elapsed_ms_whole().abs_diff(1_000) < 10); // Allow for some error (± 10 milliseconds)
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// assert_eq!(sw.elapsed(), Duration::from_secs(1)); // The elapsed time does not change while the stopwatch is stopped
 	/// ```
-	pub fn new() -> Stopwatch {
-		Stopwatch::default()
+	pub fn elapsed(&self) -> C::Duration {
+		match (self.is_running, self.timer) {
+			(true, Some(timer)) => {
+				self.elapsed + self.clock.duration_between(timer, self.clock.now())
+			}
+			_ => self.elapsed,
+		}
 	}
 
-	/// Creates a new stopwatch and starts it.
+	/// Returns the total elapsed time, or an error if the clock could not be read, the time could not be measured reliably, or the elapsed time overflowed.
 	///
 	/// # Example
 	/// ```rust
-	/// use ticky::Stopwatch;
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch, TickyError};
 	///
-	/// let mut sw = Stopwatch::start_new(); // Create a new stopwatch, and start it
-	/// std::thread::sleep(std::time::Duration::from_millis(1_000)); // Wait for 1 second
-	/// sw.stop(); // Stop the stopwatch
-	/// assert!(sw.elapsed_ms_whole().abs_diff(1_000) < 10); // Allow for some error (± 10 milliseconds)
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
+	/// sw.start(); // Start the stopwatch
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// assert_eq!(sw.try_elapsed(), Ok(Duration::from_secs(1)));
+	/// sw.elapsed = Duration::MAX; // Pretend the stopwatch has been running for a very long time
+	/// assert_eq!(sw.try_elapsed(), Err(TickyError::Overflow)); // The elapsed time cannot be represented
 	/// ```
-	pub fn start_new() -> Stopwatch {
-		let mut sw = Stopwatch::new();
-		sw.start();
-		sw
+	pub fn try_elapsed(&self) -> Result<C::Duration, TickyError> {
+		match (self.is_running, self.timer) {
+			(true, Some(timer)) => {
+				let now = self.clock.try_now()?;
+				self.elapsed
+					.checked_add(self.clock.try_duration_between(timer, now)?)
+					.ok_or(TickyError::Overflow)
+			}
+			_ => Ok(self.elapsed),
+		}
 	}
 
 	/// Resets the stopwatch.
 	///
 	/// # Example
 	/// ```rust
-	/// use ticky::Stopwatch;
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
 	///
-	/// let mut sw = Stopwatch::new(); // Create a new stopwatch
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
 	/// sw.start(); // Start the stopwatch
-	/// std::thread::sleep(std::time::Duration::from_millis(1_000)); // Wait for 1 second
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
 	/// sw.stop(); // Stop the stopwatch
-	/// assert!(sw.elapsed_ms_whole().abs_diff(1_000) < 10); // Allow for some error (± 10 milliseconds)
+	/// assert_eq!(sw.elapsed(), Duration::from_secs(1));
 	/// sw.reset(); // Reset the stopwatch
-	/// assert!(sw.elapsed_ms_whole().abs_diff(0) < 10); // Allow for some error (± 10 milliseconds)
+	/// assert_eq!(sw.elapsed(), Duration::ZERO);
 	/// sw.start(); // Start the stopwatch
-	/// std::thread::sleep(std::time::Duration::from_millis(1_000)); // Wait for 1 second
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
 	/// sw.stop(); // Stop the stopwatch
-	/// assert!(sw.elapsed_ms_whole().abs_diff(1_000) < 10); // Allow for some error (± 10 milliseconds)
-	pub fn reset(&mut self) {
-		*self = Stopwatch::new();
+	/// assert_eq!(sw.elapsed(), Duration::from_secs(1));
+	/// ```
+	pub fn reset(&mut self)
+	where
+		L: Laps<C::Duration>,
+	{
+		self.elapsed = C::Duration::ZERO;
+		self.timer = None;
+		self.is_running = false;
+		self.lap_elapsed = C::Duration::ZERO;
+		self.laps.clear();
 	}
 
 	/// Resets and starts the stopwatch.
 	///
 	/// # Example
 	/// ```rust
-	/// use ticky::Stopwatch;
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
 	///
-	/// let mut sw = Stopwatch::new(); // Create a new stopwatch
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
 	/// sw.start(); // Start the stopwatch
-	/// std::thread::sleep(std::time::Duration::from_millis(1_000)); // Wait for 1 second
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
 	/// sw.stop(); // Stop the stopwatch
-	/// assert!(sw.elapsed_ms_whole().abs_diff(1_000) < 10); // Allow for some error (± 10 milliseconds)
+	/// assert_eq!(sw.elapsed(), Duration::from_secs(1));
 	/// sw.restart(); // Reset and start the stopwatch
-	/// std::thread::sleep(std::time::Duration::from_millis(1_000)); // Wait for 1 second
+	/// clock.advance(Duration::from_secs(2)); // Move time forward by 2 seconds
 	/// sw.stop(); // Stop the stopwatch
-	/// assert!(sw.elapsed_ms_whole().abs_diff(1_000) < 10); // Allow for some error (± 10 milliseconds)
-	pub fn restart(&mut self) {
+	/// assert_eq!(sw.elapsed(), Duration::from_secs(2)); // Only the time since the restart is counted
+	/// ```
+	pub fn restart(&mut self)
+	where
+		L: Laps<C::Duration>,
+	{
 		self.reset();
 		self.start();
 	}
@@ -0,0 +1,453 @@
+/*
+	This file is part of Ticky.
+	Ticky is free software: you can redistribute it and/or modify
+	it under the terms of the GNU Affero General Public License as published by
+	the Free Software Foundation, either version 3 of the License, or
+	(at your option) any later version.
+	Ticky is distributed in the hope that it will be useful,
+	but WITHOUT ANY WARRANTY; without even the implied warranty of
+	MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+	GNU Affero General Public License for more details.
+	You should have received a copy of the GNU Affero General Public License
+	along with Ticky.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::{Clock, ClockDuration, Stopwatch, TickyError};
+use core::fmt::{Display, Formatter};
+use core::ops::{Add, AddAssign};
+use core::time::Duration;
+
+#[derive(Ord, Eq, PartialEq, PartialOrd, Clone, Copy, Debug, Hash, Default)]
+/// A Linux clock that can be read with `clock_gettime`.
+pub enum ClockId {
+	/// `CLOCK_MONOTONIC`: time since an unspecified point, excluding time suspended, and slewed by NTP.
+	#[default]
+	Monotonic,
+	/// `CLOCK_MONOTONIC_RAW`: like [`Monotonic`](ClockId::Monotonic), but not slewed by NTP.
+	MonotonicRaw,
+	/// `CLOCK_MONOTONIC_COARSE`: like [`Monotonic`](ClockId::Monotonic), but faster to read and less precise.
+	MonotonicCoarse,
+	/// `CLOCK_BOOTTIME`: like [`Monotonic`](ClockId::Monotonic), but including time suspended.
+	Boottime,
+	/// `CLOCK_PROCESS_CPUTIME_ID`: CPU time consumed by all threads in the current process.
+	ProcessCpuTime,
+	/// `CLOCK_THREAD_CPUTIME_ID`: CPU time consumed by the thread reading the clock.
+	ThreadCpuTime,
+}
+
+impl ClockId {
+	/// Returns the identifier `clock_gettime` knows this clock by.
+	fn raw(self) -> libc::clockid_t {
+		match self {
+			ClockId::Monotonic => libc::CLOCK_MONOTONIC,
+			ClockId::MonotonicRaw => libc::CLOCK_MONOTONIC_RAW,
+			ClockId::MonotonicCoarse => libc::CLOCK_MONOTONIC_COARSE,
+			ClockId::Boottime => libc::CLOCK_BOOTTIME,
+			ClockId::ProcessCpuTime => libc::CLOCK_PROCESS_CPUTIME_ID,
+			ClockId::ThreadCpuTime => libc::CLOCK_THREAD_CPUTIME_ID,
+		}
+	}
+}
+
+#[derive(Ord, Eq, PartialEq, PartialOrd, Clone, Copy, Debug, Hash, Default)]
+/// A clock backed by `clock_gettime`, reading whichever Linux clock is chosen.
+///
+/// ## Usage
+/// ```rust
+/// use core::time::Duration;
+/// use ticky::{ClockId, LinuxClock, Stopwatch};
+///
+/// let clock = LinuxClock::new(ClockId::MonotonicCoarse); // Create a new clock reading `CLOCK_MONOTONIC_COARSE`
+/// let mut sw = Stopwatch::with_clock(clock); // Create a new stopwatch using the clock
+/// sw.start(); // Start the stopwatch
+/// std::thread::sleep(Duration::from_millis(10)); // Wait for 10 milliseconds
+/// sw.stop(); // Stop the stopwatch
+/// assert!(sw.elapsed() + clock.resolution() >= Duration::from_millis(10)); // Allow for the clock's resolution
+/// ```
+pub struct LinuxClock {
+	/// The clock being read.
+	pub id: ClockId,
+}
+
+impl LinuxClock {
+	/// Creates a new clock reading the given Linux clock.
+	///
+	/// # Example
+	/// ```rust
+	/// use ticky::{ClockId, LinuxClock};
+	///
+	/// let clock = LinuxClock::new(ClockId::Boottime); // Create a new clock reading `CLOCK_BOOTTIME`
+	/// assert_eq!(clock.id, ClockId::Boottime);
+	/// ```
+	pub const fn new(id: ClockId) -> LinuxClock {
+		LinuxClock { id }
+	}
+
+	/// Returns the resolution of the clock.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{ClockId, LinuxClock};
+	///
+	/// let clock = LinuxClock::new(ClockId::MonotonicRaw); // Create a new clock reading `CLOCK_MONOTONIC_RAW`
+	/// assert!(clock.resolution() > Duration::ZERO);
+	/// ```
+	pub fn resolution(&self) -> Duration {
+		let mut ts = libc::timespec {
+			tv_sec: 0,
+			tv_nsec: 0,
+		};
+		// SAFETY: `ts` is a valid, writable `timespec`.
+		match unsafe { libc::clock_getres(self.id.raw(), &mut ts) } {
+			0 => timespec_to_duration(ts),
+			_ => Duration::ZERO,
+		}
+	}
+}
+
+impl Clock for LinuxClock {
+	type Instant = Duration;
+	type Duration = Duration;
+
+	fn now(&self) -> Self::Instant {
+		self.try_now().unwrap()
+	}
+
+	fn try_now(&self) -> Result<Self::Instant, TickyError> {
+		let mut ts = libc::timespec {
+			tv_sec: 0,
+			tv_nsec: 0,
+		};
+		// SAFETY: `ts` is a valid, writable `timespec`.
+		match unsafe { libc::clock_gettime(self.id.raw(), &mut ts) } {
+			0 => Ok(timespec_to_duration(ts)),
+			_ => Err(TickyError::ClockUnavailable),
+		}
+	}
+
+	fn duration_between(&self, earlier: Self::Instant, later: Self::Instant) -> Self::Duration {
+		later.saturating_sub(earlier)
+	}
+}
+
+impl Stopwatch<LinuxClock> {
+	/// Creates a new stopwatch measuring the CPU time consumed by the current process.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::Stopwatch;
+	///
+	/// let mut sw = Stopwatch::new_process_cpu(); // Create a new stopwatch measuring process CPU time
+	/// sw.start(); // Start the stopwatch
+	/// std::thread::sleep(Duration::from_millis(50)); // Sleeping does not consume CPU time
+	/// sw.stop(); // Stop the stopwatch
+	/// assert!(sw.elapsed() < Duration::from_millis(50));
+	/// ```
+	pub fn new_process_cpu() -> Stopwatch<LinuxClock> {
+		Stopwatch::with_clock(LinuxClock::new(ClockId::ProcessCpuTime))
+	}
+
+	/// Creates a new stopwatch measuring the CPU time consumed by the current thread.
+	///
+	/// The stopwatch should be started and stopped on the same thread; otherwise, it subtracts one thread's CPU time from another's.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::Stopwatch;
+	///
+	/// let mut sw = Stopwatch::new_thread_cpu(); // Create a new stopwatch measuring thread CPU time
+	/// sw.start(); // Start the stopwatch
+	/// std::thread::sleep(Duration::from_millis(50)); // Sleeping does not consume CPU time
+	/// sw.stop(); // Stop the stopwatch
+	/// assert!(sw.elapsed() < Duration::from_millis(50));
+	/// ```
+	pub fn new_thread_cpu() -> Stopwatch<LinuxClock> {
+		Stopwatch::with_clock(LinuxClock::new(ClockId::ThreadCpuTime))
+	}
+}
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Hash, Default)]
+/// An amount of wall time, and the CPU time consumed during it.
+pub struct WallCpuDuration {
+	/// The wall time that passed.
+	pub wall: Duration,
+	/// The CPU time consumed.
+	pub cpu: Duration,
+}
+
+impl WallCpuDuration {
+	/// Returns the CPU time consumed as a fraction of the wall time that passed, or `None` if no wall time has passed.
+	///
+	/// With several threads busy at once, this can be greater than one.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::WallCpuDuration;
+	///
+	/// let dur = WallCpuDuration {
+	///     wall: Duration::from_secs(4),
+	///     cpu: Duration::from_secs(1),
+	/// };
+	/// assert_eq!(dur.utilization(), Some(0.25));
+	/// assert_eq!(WallCpuDuration::default().utilization(), None); // Nothing has been measured yet
+	/// ```
+	pub fn utilization(&self) -> Option<f64> {
+		(!self.wall.is_zero()).then(|| self.cpu.as_secs_f64() / self.wall.as_secs_f64())
+	}
+}
+
+impl Add for WallCpuDuration {
+	type Output = WallCpuDuration;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		WallCpuDuration {
+			wall: self.wall + rhs.wall,
+			cpu: self.cpu + rhs.cpu,
+		}
+	}
+}
+
+impl AddAssign for WallCpuDuration {
+	fn add_assign(&mut self, rhs: Self) {
+		*self = *self + rhs;
+	}
+}
+
+impl ClockDuration for WallCpuDuration {
+	const ZERO: Self = WallCpuDuration {
+		wall: Duration::ZERO,
+		cpu: Duration::ZERO,
+	};
+
+	fn checked_add(self, rhs: Self) -> Option<Self> {
+		Some(WallCpuDuration {
+			wall: self.wall.checked_add(rhs.wall)?,
+			cpu: self.cpu.checked_add(rhs.cpu)?,
+		})
+	}
+}
+
+#[derive(Ord, Eq, PartialEq, PartialOrd, Clone, Copy, Debug, Hash)]
+/// A clock measuring wall time and CPU time side by side.
+///
+/// Wall time is read from `CLOCK_MONOTONIC`, and CPU time from either the process or the thread CPU clock.
+///
+/// ## Usage
+/// ```rust
+/// use core::time::Duration;
+/// use ticky::{Stopwatch, WallCpuClock};
+///
+/// let mut sw = Stopwatch::with_clock(WallCpuClock::process()); // Create a new stopwatch measuring wall and process CPU time
+/// sw.start(); // Start the stopwatch
+/// std::thread::sleep(Duration::from_millis(50)); // Sleeping passes wall time, but does not consume CPU time
+/// sw.stop(); // Stop the stopwatch
+/// let elapsed = sw.elapsed();
+/// assert!(elapsed.wall >= Duration::from_millis(50));
+/// assert!(elapsed.cpu < elapsed.wall);
+/// ```
+pub struct WallCpuClock {
+	/// The clock CPU time is read from.
+	pub cpu: LinuxClock,
+}
+
+impl Default for WallCpuClock {
+	fn default() -> Self {
+		WallCpuClock::process()
+	}
+}
+
+impl WallCpuClock {
+	/// Creates a new clock measuring wall time and the CPU time consumed by the current process.
+	///
+	/// # Example
+	/// ```rust
+	/// use ticky::{ClockId, WallCpuClock};
+	///
+	/// let clock = WallCpuClock::process(); // Create a new clock measuring process CPU time
+	/// assert_eq!(clock.cpu.id, ClockId::ProcessCpuTime);
+	/// ```
+	pub const fn process() -> WallCpuClock {
+		WallCpuClock {
+			cpu: LinuxClock::new(ClockId::ProcessCpuTime),
+		}
+	}
+
+	/// Creates a new clock measuring wall time and the CPU time consumed by the current thread.
+	///
+	/// # Example
+	/// ```rust
+	/// use ticky::{ClockId, WallCpuClock};
+	///
+	/// let clock = WallCpuClock::thread(); // Create a new clock measuring thread CPU time
+	/// assert_eq!(clock.cpu.id, ClockId::ThreadCpuTime);
+	/// ```
+	pub const fn thread() -> WallCpuClock {
+		WallCpuClock {
+			cpu: LinuxClock::new(ClockId::ThreadCpuTime),
+		}
+	}
+}
+
+impl Clock for WallCpuClock {
+	type Instant = WallCpuDuration;
+	type Duration = WallCpuDuration;
+
+	fn now(&self) -> Self::Instant {
+		self.try_now().unwrap()
+	}
+
+	fn try_now(&self) -> Result<Self::Instant, TickyError> {
+		Ok(WallCpuDuration {
+			wall: LinuxClock::new(ClockId::Monotonic).try_now()?,
+			cpu: self.cpu.try_now()?,
+		})
+	}
+
+	fn duration_between(&self, earlier: Self::Instant, later: Self::Instant) -> Self::Duration {
+		WallCpuDuration {
+			wall: later.wall.saturating_sub(earlier.wall),
+			cpu: later.cpu.saturating_sub(earlier.cpu),
+		}
+	}
+}
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Hash, Default)]
+/// An amount of time, and how much of it the system was awake for.
+pub struct SuspendAwareDuration {
+	/// The time that passed, including time the system was suspended.
+	pub total: Duration,
+	/// The time that passed while the system was awake.
+	pub awake: Duration,
+}
+
+impl SuspendAwareDuration {
+	/// Returns the time that passed while the system was suspended.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::SuspendAwareDuration;
+	///
+	/// let dur = SuspendAwareDuration {
+	///     total: Duration::from_secs(3 * 60 * 60),
+	///     awake: Duration::from_secs(140 * 60),
+	/// };
+	/// assert_eq!(dur.suspended(), Duration::from_secs(40 * 60));
+	/// ```
+	pub fn suspended(&self) -> Duration {
+		self.total.saturating_sub(self.awake)
+	}
+}
+
+impl Display for SuspendAwareDuration {
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		write!(
+			f,
+			"{:?} elapsed, of which {:?} suspended",
+			self.total,
+			self.suspended()
+		)
+	}
+}
+
+impl Add for SuspendAwareDuration {
+	type Output = SuspendAwareDuration;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		SuspendAwareDuration {
+			total: self.total + rhs.total,
+			awake: self.awake + rhs.awake,
+		}
+	}
+}
+
+impl AddAssign for SuspendAwareDuration {
+	fn add_assign(&mut self, rhs: Self) {
+		*self = *self + rhs;
+	}
+}
+
+impl ClockDuration for SuspendAwareDuration {
+	const ZERO: Self = SuspendAwareDuration {
+		total: Duration::ZERO,
+		awake: Duration::ZERO,
+	};
+
+	fn checked_add(self, rhs: Self) -> Option<Self> {
+		Some(SuspendAwareDuration {
+			total: self.total.checked_add(rhs.total)?,
+			awake: self.awake.checked_add(rhs.awake)?,
+		})
+	}
+}
+
+#[derive(Ord, Eq, PartialEq, PartialOrd, Clone, Copy, Debug, Hash, Default)]
+/// A clock measuring time both including and excluding time the system was suspended.
+///
+/// Time including suspension is read from `CLOCK_BOOTTIME`, and time excluding it from `CLOCK_MONOTONIC`.
+///
+/// ## Usage
+/// ```rust
+/// use core::time::Duration;
+/// use ticky::Stopwatch;
+///
+/// let mut sw = Stopwatch::new_suspend_aware(); // Create a new stopwatch measuring suspended time separately
+/// sw.start(); // Start the stopwatch
+/// std::thread::sleep(Duration::from_millis(10)); // Wait for 10 milliseconds
+/// sw.stop(); // Stop the stopwatch
+/// let elapsed = sw.elapsed();
+/// assert!(elapsed.total >= Duration::from_millis(10));
+/// println!("{elapsed}"); // For example, "10.1ms elapsed, of which 0ns suspended"
+/// ```
+pub struct SuspendAwareClock;
+
+impl Clock for SuspendAwareClock {
+	type Instant = SuspendAwareDuration;
+	type Duration = SuspendAwareDuration;
+
+	fn now(&self) -> Self::Instant {
+		self.try_now().unwrap()
+	}
+
+	fn try_now(&self) -> Result<Self::Instant, TickyError> {
+		Ok(SuspendAwareDuration {
+			total: LinuxClock::new(ClockId::Boottime).try_now()?,
+			awake: LinuxClock::new(ClockId::Monotonic).try_now()?,
+		})
+	}
+
+	fn duration_between(&self, earlier: Self::Instant, later: Self::Instant) -> Self::Duration {
+		SuspendAwareDuration {
+			total: later.total.saturating_sub(earlier.total),
+			awake: later.awake.saturating_sub(earlier.awake),
+		}
+	}
+}
+
+impl Stopwatch<SuspendAwareClock> {
+	/// Creates a new stopwatch measuring time both including and excluding time the system was suspended.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::Stopwatch;
+	///
+	/// let mut sw = Stopwatch::new_suspend_aware(); // Create a new stopwatch measuring suspended time separately
+	/// sw.start(); // Start the stopwatch
+	/// std::thread::sleep(Duration::from_millis(10)); // Wait for 10 milliseconds
+	/// sw.stop(); // Stop the stopwatch
+	/// assert!(sw.elapsed().awake >= Duration::from_millis(10));
+	/// ```
+	pub fn new_suspend_aware() -> Stopwatch<SuspendAwareClock> {
+		Stopwatch::with_clock(SuspendAwareClock)
+	}
+}
+
+/// Converts a `timespec` reported by the kernel to a duration.
+fn timespec_to_duration(ts: libc::timespec) -> Duration {
+	Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32)
+}
@@ -0,0 +1,142 @@
+/*
+	This file is part of Ticky.
+	Ticky is free software: you can redistribute it and/or modify
+	it under the terms of the GNU Affero General Public License as published by
+	the Free Software Foundation, either version 3 of the License, or
+	(at your option) any later version.
+	Ticky is distributed in the hope that it will be useful,
+	but WITHOUT ANY WARRANTY; without even the implied warranty of
+	MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+	GNU Affero General Public License for more details.
+	You should have received a copy of the GNU Affero General Public License
+	along with Ticky.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::{Clock, Stopwatch};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(any(feature = "stdtime", all(feature = "hifitime", feature = "std")))]
+/// Evaluates an expression, prints how long it took to standard error, and returns its value.
+///
+/// Like [`dbg!`](https://doc.rust-lang.org/std/macro.dbg.html), the message includes the file and line of the call and the expression itself, and the macro can be wrapped around any expression without changing what it does.
+///
+/// # Example
+/// ```rust
+/// use ticky::time;
+///
+/// let sum = time!((1..=100).sum::<u32>()); // Prints something like `[src/main.rs:3:11] (1..=100).sum::<u32>() took 1.2µs`
+/// assert_eq!(sum, 5_050);
+/// ```
+#[macro_export]
+macro_rules! time {
+	($expr:expr $(,)?) => {{
+		let mut sw = $crate::Stopwatch::start_new();
+		let result = $expr;
+		sw.stop();
+		::std::eprintln!(
+			"[{}:{}:{}] {} took {:?}",
+			::core::file!(),
+			::core::line!(),
+			::core::column!(),
+			::core::stringify!($expr),
+			sw.elapsed()
+		);
+		result
+	}};
+}
+
+#[cfg(any(feature = "stdtime", all(feature = "hifitime", feature = "std")))]
+impl Stopwatch {
+	/// Runs the given function, returning its result and how long it took.
+	///
+	/// # Example
+	/// ```rust
+	/// use ticky::Stopwatch;
+	///
+	/// let (answer, elapsed) = Stopwatch::measure(|| {
+	///     std::thread::sleep(std::time::Duration::from_millis(10)); // Wait for 10 milliseconds
+	///     42
+	/// });
+	/// assert_eq!(answer, 42);
+	/// assert!(elapsed.as_millis() >= 10); // At least 10 milliseconds have passed
+	/// ```
+	pub fn measure<R>(f: impl FnOnce() -> R) -> (R, <crate::DefaultClock as Clock>::Duration) {
+		Stopwatch::measure_with_clock(crate::DEFAULT_CLOCK, f)
+	}
+
+	#[cfg(feature = "alloc")]
+	/// Runs the given function the given number of times, returning how long each run took.
+	///
+	/// # Example
+	/// ```rust
+	/// use ticky::Stopwatch;
+	///
+	/// let times = Stopwatch::measure_n(5, || std::thread::sleep(std::time::Duration::from_millis(10))); // Time 5 runs
+	/// assert_eq!(times.len(), 5);
+	/// assert!(times.iter().all(|time| time.as_millis() >= 10)); // Each run slept for at least 10 milliseconds
+	/// ```
+	pub fn measure_n<R>(
+		n: usize,
+		f: impl FnMut() -> R,
+	) -> Vec<<crate::DefaultClock as Clock>::Duration> {
+		Stopwatch::measure_n_with_clock(crate::DEFAULT_CLOCK, n, f)
+	}
+}
+
+impl<C: Clock> Stopwatch<C> {
+	/// Runs the given function, returning its result and how long it took according to the given clock.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let (answer, elapsed) = Stopwatch::measure_with_clock(clock.clone(), || {
+	///     clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	///     42
+	/// });
+	/// assert_eq!(answer, 42);
+	/// assert_eq!(elapsed, Duration::from_secs(1));
+	/// ```
+	pub fn measure_with_clock<R>(clock: C, f: impl FnOnce() -> R) -> (R, C::Duration) {
+		let mut sw = Stopwatch::with_clock(clock);
+		sw.start();
+		let result = f();
+		sw.stop();
+		(result, sw.elapsed())
+	}
+
+	#[cfg(feature = "alloc")]
+	/// Runs the given function the given number of times, returning how long each run took according to the given clock.
+	///
+	/// Runs are timed back to back, reading the clock once between each, and their results are discarded.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut millis = 0;
+	/// let times = Stopwatch::measure_n_with_clock(clock.clone(), 3, || {
+	///     millis += 100;
+	///     clock.advance(Duration::from_millis(millis)); // Each run takes 100 milliseconds longer than the last
+	/// });
+	/// assert_eq!(times, [Duration::from_millis(100), Duration::from_millis(200), Duration::from_millis(300)]);
+	/// ```
+	pub fn measure_n_with_clock<R>(
+		clock: C,
+		n: usize,
+		mut f: impl FnMut() -> R,
+	) -> Vec<C::Duration> {
+		let mut sw = Stopwatch::with_clock(clock).with_laps(Vec::with_capacity(n));
+		sw.start();
+		for _ in 0..n {
+			core::hint::black_box(f());
+			sw.lap();
+		}
+		sw.laps
+	}
+}
@@ -0,0 +1,142 @@
+/*
+	This file is part of Ticky.
+	Ticky is free software: you can redistribute it and/or modify
+	it under the terms of the GNU Affero General Public License as published by
+	the Free Software Foundation, either version 3 of the License, or
+	(at your option) any later version.
+	Ticky is distributed in the hope that it will be useful,
+	but WITHOUT ANY WARRANTY; without even the implied warranty of
+	MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+	GNU Affero General Public License for more details.
+	You should have received a copy of the GNU Affero General Public License
+	along with Ticky.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::Clock;
+use core::sync::atomic::{AtomicU64, Ordering};
+use core::time::Duration;
+use std::sync::Arc;
+
+#[derive(Clone, Debug, Default)]
+/// A clock that only moves when told to, for deterministic tests.
+///
+/// Clones of a mock clock share the same time, so a clone can be handed to a [`Stopwatch`](crate::Stopwatch) while the original is used to move time forward.
+///
+/// ## Usage
+/// ```rust
+/// use core::time::Duration;
+/// use ticky::{MockClock, Stopwatch};
+///
+/// let clock = MockClock::new(); // Create a new mock clock
+/// let mut sw = Stopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
+/// sw.start(); // Start the stopwatch
+/// clock.advance(Duration::from_millis(1_500)); // Move time forward by 1.5 seconds
+/// sw.stop(); // Stop the stopwatch
+/// assert_eq!(sw.elapsed(), Duration::from_millis(1_500));
+/// clock.advance(Duration::from_secs(60)); // Time passing while stopped is not counted
+/// assert_eq!(sw.elapsed(), Duration::from_millis(1_500));
+/// sw.restart(); // Reset and start the stopwatch
+/// clock.advance(Duration::from_millis(250)); // Move time forward by 250 milliseconds
+/// assert_eq!(sw.elapsed(), Duration::from_millis(250));
+/// sw.reset(); // Reset the stopwatch
+/// assert_eq!(sw.elapsed(), Duration::ZERO);
+/// ```
+pub struct MockClock {
+	/// The time since the clock was created, in nanoseconds.
+	nanos: Arc<AtomicU64>,
+}
+
+impl MockClock {
+	/// Creates a new mock clock, starting at zero.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::MockClock;
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// assert_eq!(clock.time(), Duration::ZERO);
+	/// ```
+	pub fn new() -> MockClock {
+		MockClock::default()
+	}
+
+	/// Returns the time since the clock was created, as currently set.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::MockClock;
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// clock.advance(Duration::from_secs(3)); // Move time forward by 3 seconds
+	/// assert_eq!(clock.time(), Duration::from_secs(3));
+	/// ```
+	pub fn time(&self) -> Duration {
+		Duration::from_nanos(self.nanos.load(Ordering::SeqCst))
+	}
+
+	/// Moves the clock (and all of its clones) forward by the given amount of time.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::MockClock;
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let shared = clock.clone(); // Clone the mock clock, sharing its time
+	/// clock.advance(Duration::from_millis(10)); // Move time forward by 10 milliseconds
+	/// assert_eq!(shared.time(), Duration::from_millis(10));
+	/// ```
+	pub fn advance(&self, by: Duration) {
+		let by = saturating_nanos(by);
+		let mut current = self.nanos.load(Ordering::SeqCst);
+		while let Err(actual) = self.nanos.compare_exchange_weak(
+			current,
+			current.saturating_add(by),
+			Ordering::SeqCst,
+			Ordering::SeqCst,
+		) {
+			current = actual;
+		}
+	}
+
+	/// Sets the clock (and all of its clones) to the given time since the clock was created.
+	///
+	/// Setting the clock backwards is allowed; a stopwatch measuring across such a jump sees no time pass.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
+	/// clock.set(Duration::from_secs(10)); // Set the time to 10 seconds
+	/// sw.start(); // Start the stopwatch
+	/// clock.set(Duration::from_secs(12)); // Set the time to 12 seconds
+	/// sw.stop(); // Stop the stopwatch
+	/// assert_eq!(sw.elapsed(), Duration::from_secs(2));
+	/// ```
+	pub fn set(&self, to: Duration) {
+		self.nanos.store(saturating_nanos(to), Ordering::SeqCst);
+	}
+}
+
+impl Clock for MockClock {
+	type Instant = Duration;
+	type Duration = Duration;
+
+	fn now(&self) -> Self::Instant {
+		self.time()
+	}
+
+	fn duration_between(&self, earlier: Self::Instant, later: Self::Instant) -> Self::Duration {
+		later.saturating_sub(earlier)
+	}
+}
+
+/// Converts a duration to whole nanoseconds, saturating at [`u64::MAX`].
+fn saturating_nanos(dur: Duration) -> u64 {
+	dur.as_nanos().try_into().unwrap_or(u64::MAX)
+}
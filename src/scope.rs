@@ -0,0 +1,196 @@
+/*
+	This file is part of Ticky.
+	Ticky is free software: you can redistribute it and/or modify
+	it under the terms of the GNU Affero General Public License as published by
+	the Free Software Foundation, either version 3 of the License, or
+	(at your option) any later version.
+	Ticky is distributed in the hope that it will be useful,
+	but WITHOUT ANY WARRANTY; without even the implied warranty of
+	MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+	GNU Affero General Public License for more details.
+	You should have received a copy of the GNU Affero General Public License
+	along with Ticky.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::{Clock, Stopwatch};
+use core::ops::{Deref, DerefMut};
+
+#[must_use = "the guard stops timing when dropped"]
+/// A guard that keeps a [`Stopwatch`] running until it is dropped, returned by [`Stopwatch::scoped`].
+///
+/// The stopwatch is paused when the guard goes out of scope, including through an early `return` or `?`. While the guard is alive, the stopwatch can be used through it.
+///
+/// ## Usage
+/// ```rust
+/// use core::time::Duration;
+/// use ticky::{MockClock, Stopwatch};
+///
+/// let clock = MockClock::new(); // Create a new mock clock
+/// let mut sw = Stopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
+///
+/// let parse = |input: &str, sw: &mut Stopwatch<MockClock>| -> Result<u32, std::num::ParseIntError> {
+///     let _guard = sw.scoped(); // Time the rest of this closure
+///     clock.advance(Duration::from_secs(1)); // Spend 1 second parsing
+///     Ok(input.parse()?)
+/// };
+/// assert!(parse("not a number", &mut sw).is_err()); // Return early with an error
+/// assert_eq!(sw.is_running(), false); // The stopwatch was paused anyway
+/// assert_eq!(sw.elapsed(), Duration::from_secs(1));
+/// ```
+pub struct ScopeGuard<'a, C: Clock, L> {
+	/// The stopwatch being timed.
+	stopwatch: &'a mut Stopwatch<C, L>,
+	/// Whether the stopwatch was already running when the guard was created.
+	was_running: bool,
+}
+
+#[must_use = "the guard stops timing when dropped"]
+/// A guard that times until it is dropped, then hands the elapsed time to a callback, returned by [`Stopwatch::scoped_with`].
+///
+/// The callback is run when the guard goes out of scope, including through an early `return` or `?`, so it can be used to send measurements to a sink such as a log or a metrics collector. While the guard is alive, its stopwatch can be used through it.
+///
+/// ## Usage
+/// ```rust
+/// use core::time::Duration;
+/// use ticky::{MockClock, Stopwatch};
+///
+/// let clock = MockClock::new(); // Create a new mock clock
+/// let mut timings = Vec::new(); // Somewhere to collect measurements
+/// for millis in [100, 200] {
+///     let _guard = Stopwatch::with_clock(clock.clone()).scoped_with(|elapsed| timings.push(elapsed)); // Time this iteration
+///     clock.advance(Duration::from_millis(millis)); // Move time forward
+/// }
+/// assert_eq!(timings, [Duration::from_millis(100), Duration::from_millis(200)]);
+/// ```
+pub struct ReportGuard<C: Clock, L, F: FnOnce(C::Duration)> {
+	/// The stopwatch doing the timing.
+	stopwatch: Stopwatch<C, L>,
+	/// The callback to hand the elapsed time to, until the guard is dropped.
+	report: Option<F>,
+}
+
+impl<C: Clock, L> Stopwatch<C, L> {
+	#[must_use = "the guard stops timing when dropped"]
+	/// Starts the stopwatch, returning a guard that pauses it when dropped.
+	///
+	/// If the stopwatch is already running, it is left running when the guard is dropped.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
+	/// {
+	///     let guard = sw.scoped(); // Start the stopwatch until the end of this block
+	///     clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	///     assert_eq!(guard.elapsed(), Duration::from_secs(1)); // The stopwatch can be read through the guard
+	/// }
+	/// clock.advance(Duration::from_secs(1)); // Time passing after the block is not counted
+	/// assert_eq!(sw.elapsed(), Duration::from_secs(1));
+	/// ```
+	pub fn scoped(&mut self) -> ScopeGuard<'_, C, L> {
+		let was_running = self.is_running;
+		if !was_running {
+			self.start();
+		}
+		ScopeGuard {
+			stopwatch: self,
+			was_running,
+		}
+	}
+
+	#[must_use = "the guard stops timing when dropped"]
+	/// Starts the stopwatch, returning a guard that stops it when dropped and hands the total elapsed time to the given callback.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut total = Duration::ZERO; // Somewhere to collect the measurement
+	/// {
+	///     let _guard = Stopwatch::with_clock(clock.clone()).scoped_with(|elapsed| total += elapsed); // Time until the end of this block
+	///     clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// }
+	/// assert_eq!(total, Duration::from_secs(1));
+	/// ```
+	pub fn scoped_with<F: FnOnce(C::Duration)>(mut self, report: F) -> ReportGuard<C, L, F> {
+		if !self.is_running {
+			self.start();
+		}
+		ReportGuard {
+			stopwatch: self,
+			report: Some(report),
+		}
+	}
+}
+
+impl<C: Clock, L, F: FnOnce(C::Duration)> ReportGuard<C, L, F> {
+	/// Stops the stopwatch without running the callback, returning the total elapsed time.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut reported = false;
+	/// let guard = Stopwatch::with_clock(clock.clone()).scoped_with(|_| reported = true); // Start timing
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// assert_eq!(guard.cancel(), Duration::from_secs(1)); // Decide not to report this measurement
+	/// assert_eq!(reported, false);
+	/// ```
+	pub fn cancel(mut self) -> C::Duration {
+		self.report = None;
+		self.stopwatch.stop();
+		self.stopwatch.elapsed()
+	}
+}
+
+impl<C: Clock, L> Deref for ScopeGuard<'_, C, L> {
+	type Target = Stopwatch<C, L>;
+
+	fn deref(&self) -> &Self::Target {
+		self.stopwatch
+	}
+}
+
+impl<C: Clock, L> DerefMut for ScopeGuard<'_, C, L> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		self.stopwatch
+	}
+}
+
+impl<C: Clock, L> Drop for ScopeGuard<'_, C, L> {
+	fn drop(&mut self) {
+		if !self.was_running {
+			self.stopwatch.stop();
+		}
+	}
+}
+
+impl<C: Clock, L, F: FnOnce(C::Duration)> Deref for ReportGuard<C, L, F> {
+	type Target = Stopwatch<C, L>;
+
+	fn deref(&self) -> &Self::Target {
+		&self.stopwatch
+	}
+}
+
+impl<C: Clock, L, F: FnOnce(C::Duration)> DerefMut for ReportGuard<C, L, F> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.stopwatch
+	}
+}
+
+impl<C: Clock, L, F: FnOnce(C::Duration)> Drop for ReportGuard<C, L, F> {
+	fn drop(&mut self) {
+		if let Some(report) = self.report.take() {
+			self.stopwatch.stop();
+			report(self.stopwatch.elapsed());
+		}
+	}
+}
@@ -0,0 +1,375 @@
+/*
+	This file is part of Ticky.
+	Ticky is free software: you can redistribute it and/or modify
+	it under the terms of the GNU Affero General Public License as published by
+	the Free Software Foundation, either version 3 of the License, or
+	(at your option) any later version.
+	Ticky is distributed in the hope that it will be useful,
+	but WITHOUT ANY WARRANTY; without even the implied warranty of
+	MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+	GNU Affero General Public License for more details.
+	You should have received a copy of the GNU Affero General Public License
+	along with Ticky.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::{Clock, DefaultClock, ScalarDuration, Stopwatch};
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display, Formatter};
+
+#[derive(Clone)]
+/// A timer for nested spans of work, producing a tree of where time was spent.
+///
+/// Each span is timed by its own [`Stopwatch`]. Entering a span with the same name as one of the current span's children resumes that child rather than adding another, so repeated work is merged into a single node.
+///
+/// Formatting the timer with `{}` produces a table of the tree, with the inclusive time (including children), exclusive time (excluding children), and number of calls of each span.
+///
+/// ## Usage
+/// ```rust
+/// use core::time::Duration;
+/// use ticky::{MockClock, SpanTimer};
+///
+/// let clock = MockClock::new(); // Create a new mock clock
+/// let mut spans = SpanTimer::with_clock(clock.clone()); // Create a new span timer sharing the mock clock
+/// spans.span("build", |spans| {
+///     clock.advance(Duration::from_millis(500)); // Spend 500 milliseconds in `build` itself
+///     for _ in 0..2 {
+///         spans.span("compile", |_| clock.advance(Duration::from_secs(1))); // Spend 1 second compiling, twice
+///     }
+///     spans.span("link", |_| clock.advance(Duration::from_millis(250))); // Spend 250 milliseconds linking
+/// });
+///
+/// let build = spans.get(&["build"]).unwrap();
+/// assert_eq!(build.inclusive(), Duration::from_millis(2_750));
+/// assert_eq!(build.exclusive(), Duration::from_millis(500));
+/// let compile = spans.get(&["build", "compile"]).unwrap();
+/// assert_eq!(compile.calls(), 2);
+/// assert_eq!(compile.inclusive(), Duration::from_secs(2));
+/// assert_eq!(
+///     spans.to_string(),
+///     "span       calls  inclusive  exclusive\n\
+///      build          1      2.75s      500ms\n\
+///      ├ compile      2         2s         2s\n\
+///      └ link         1      250ms      250ms"
+/// );
+/// ```
+pub struct SpanTimer<C: Clock = DefaultClock> {
+	/// Every span entered so far.
+	nodes: Vec<SpanNode<C>>,
+	/// The indices of the spans with no parent, in the order they were first entered.
+	roots: Vec<usize>,
+	/// The indices of the spans currently entered, outermost first.
+	open: Vec<usize>,
+	/// The clock the spans read time from.
+	clock: C,
+}
+
+#[derive(Clone)]
+/// A span in a [`SpanTimer`].
+struct SpanNode<C: Clock> {
+	/// The name of the span.
+	name: Cow<'static, str>,
+	/// The indices of the span's children, in the order they were first entered.
+	children: Vec<usize>,
+	/// The time spent in the span, including its children.
+	stopwatch: Stopwatch<C>,
+	/// The number of times the span was entered.
+	calls: u64,
+}
+
+/// A view of one span in a [`SpanTimer`], and its children.
+pub struct Span<'a, C: Clock> {
+	/// The timer the span belongs to.
+	timer: &'a SpanTimer<C>,
+	/// The index of the span.
+	index: usize,
+}
+
+impl<C: Clock> Clone for Span<'_, C> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<C: Clock> Copy for Span<'_, C> {}
+
+#[cfg(any(feature = "stdtime", all(feature = "hifitime", feature = "std")))]
+impl SpanTimer {
+	/// Creates a new span timer, with no spans entered.
+	///
+	/// # Example
+	/// ```rust
+	/// use ticky::SpanTimer;
+	///
+	/// let mut spans = SpanTimer::new(); // Create a new span timer
+	/// spans.enter("build"); // Enter a span
+	/// std::thread::sleep(std::time::Duration::from_millis(10)); // Wait for 10 milliseconds
+	/// spans.exit(); // Exit the span
+	/// assert!(spans.get(&["build"]).unwrap().inclusive().as_millis() >= 10); // At least 10 milliseconds were spent in the span
+	/// ```
+	pub const fn new() -> SpanTimer {
+		SpanTimer::with_clock(crate::DEFAULT_CLOCK)
+	}
+}
+
+impl<C: Clock + Default> Default for SpanTimer<C> {
+	fn default() -> Self {
+		SpanTimer::with_clock(C::default())
+	}
+}
+
+impl<C: Clock> SpanTimer<C> {
+	/// Creates a new span timer that reads time from the given clock, with no spans entered.
+	///
+	/// # Example
+	/// ```rust
+	/// use ticky::{MockClock, SpanTimer};
+	///
+	/// let spans = SpanTimer::with_clock(MockClock::new()); // Create a new span timer using a mock clock
+	/// assert_eq!(spans.roots().count(), 0); // No spans have been entered
+	/// ```
+	pub const fn with_clock(clock: C) -> SpanTimer<C> {
+		SpanTimer {
+			nodes: Vec::new(),
+			roots: Vec::new(),
+			open: Vec::new(),
+			clock,
+		}
+	}
+}
+
+impl<C: Clock + Clone> SpanTimer<C>
+where
+	C::Duration: ScalarDuration,
+{
+	/// Enters a span inside the current one (or at the top level, if no span is entered).
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, SpanTimer};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut spans = SpanTimer::with_clock(clock.clone()); // Create a new span timer sharing the mock clock
+	/// spans.enter("build"); // Enter a span
+	/// spans.enter("compile"); // Enter a span inside it
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// spans.exit(); // Exit `compile`
+	/// spans.exit(); // Exit `build`
+	/// assert_eq!(spans.get(&["build", "compile"]).unwrap().inclusive(), Duration::from_secs(1));
+	/// ```
+	pub fn enter(&mut self, name: impl Into<Cow<'static, str>>) {
+		let name = name.into();
+		let siblings = match self.open.last() {
+			Some(&parent) => &self.nodes[parent].children,
+			None => &self.roots,
+		};
+		let index = match siblings
+			.iter()
+			.copied()
+			.find(|&sibling| self.nodes[sibling].name == name)
+		{
+			Some(index) => index,
+			None => {
+				let index = self.nodes.len();
+				self.nodes.push(SpanNode {
+					name,
+					children: Vec::new(),
+					stopwatch: Stopwatch::with_clock(self.clock.clone()),
+					calls: 0,
+				});
+				match self.open.last() {
+					Some(&parent) => self.nodes[parent].children.push(index),
+					None => self.roots.push(index),
+				}
+				index
+			}
+		};
+		let node = &mut self.nodes[index];
+		node.calls += 1;
+		node.stopwatch.start();
+		self.open.push(index);
+	}
+
+	/// Exits the current span, if any.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, SpanTimer};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut spans = SpanTimer::with_clock(clock.clone()); // Create a new span timer sharing the mock clock
+	/// spans.enter("build"); // Enter a span
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// spans.exit(); // Exit the span
+	/// clock.advance(Duration::from_secs(1)); // Time outside of any span is not counted
+	/// assert_eq!(spans.get(&["build"]).unwrap().inclusive(), Duration::from_secs(1));
+	/// ```
+	pub fn exit(&mut self) {
+		if let Some(index) = self.open.pop() {
+			self.nodes[index].stopwatch.stop();
+		}
+	}
+
+	/// Runs the given function inside a span, passing it the timer so that it can enter spans of its own.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, SpanTimer};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut spans = SpanTimer::with_clock(clock.clone()); // Create a new span timer sharing the mock clock
+	/// let answer = spans.span("build", |spans| {
+	///     spans.span("compile", |_| clock.advance(Duration::from_secs(1))); // Spend 1 second compiling
+	///     42
+	/// });
+	/// assert_eq!(answer, 42);
+	/// assert_eq!(spans.get(&["build"]).unwrap().inclusive(), Duration::from_secs(1));
+	/// ```
+	pub fn span<R>(
+		&mut self,
+		name: impl Into<Cow<'static, str>>,
+		f: impl FnOnce(&mut SpanTimer<C>) -> R,
+	) -> R {
+		self.enter(name);
+		let result = f(self);
+		self.exit();
+		result
+	}
+
+	/// Returns the spans with no parent, in the order they were first entered.
+	///
+	/// # Example
+	/// ```rust
+	/// use ticky::{MockClock, SpanTimer};
+	///
+	/// let mut spans = SpanTimer::with_clock(MockClock::new()); // Create a new span timer using a mock clock
+	/// spans.span("fetch", |_| ()); // Time a span
+	/// spans.span("build", |_| ()); // Time another span
+	/// spans.span("fetch", |_| ()); // Time the first span again
+	/// let names: Vec<_> = spans.roots().map(|span| span.name()).collect();
+	/// assert_eq!(names, ["fetch", "build"]);
+	/// ```
+	pub fn roots(&self) -> impl Iterator<Item = Span<'_, C>> {
+		self.roots.iter().map(|&index| Span { timer: self, index })
+	}
+
+	/// Returns the span at the given path of names from the top level, if it has been entered.
+	///
+	/// # Example
+	/// ```rust
+	/// use ticky::{MockClock, SpanTimer};
+	///
+	/// let mut spans = SpanTimer::with_clock(MockClock::new()); // Create a new span timer using a mock clock
+	/// spans.span("build", |spans| spans.span("compile", |_| ())); // Time nested spans
+	/// assert!(spans.get(&["build", "compile"]).is_some());
+	/// assert!(spans.get(&["compile"]).is_none()); // `compile` is not at the top level
+	/// ```
+	pub fn get(&self, path: &[&str]) -> Option<Span<'_, C>> {
+		let (first, rest) = path.split_first()?;
+		let mut span = self.roots().find(|span| span.name() == *first)?;
+		for name in rest {
+			span = span.children().find(|child| child.name() == *name)?;
+		}
+		Some(span)
+	}
+}
+
+impl<'a, C: Clock> Span<'a, C>
+where
+	C::Duration: ScalarDuration,
+{
+	/// Returns the node of the span.
+	fn node(&self) -> &'a SpanNode<C> {
+		&self.timer.nodes[self.index]
+	}
+
+	/// Returns the name of the span.
+	pub fn name(&self) -> &'a str {
+		&self.node().name
+	}
+
+	/// Returns the number of times the span was entered.
+	pub fn calls(&self) -> u64 {
+		self.node().calls
+	}
+
+	/// Returns the time spent in the span, including time spent in its children.
+	pub fn inclusive(&self) -> C::Duration {
+		self.node().stopwatch.elapsed()
+	}
+
+	/// Returns the time spent in the span, excluding time spent in its children.
+	pub fn exclusive(&self) -> C::Duration {
+		let children: i128 = self
+			.children()
+			.map(|child| child.inclusive().total_nanoseconds())
+			.sum();
+		C::Duration::from_total_nanoseconds(self.inclusive().total_nanoseconds() - children)
+	}
+
+	/// Returns the span's children, in the order they were first entered.
+	pub fn children(&self) -> impl Iterator<Item = Span<'a, C>> {
+		let timer = self.timer;
+		self.node()
+			.children
+			.iter()
+			.map(move |&index| Span { timer, index })
+	}
+}
+
+impl<C: Clock + Clone> Display for SpanTimer<C>
+where
+	C::Duration: ScalarDuration + Debug,
+{
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		let mut rows = Vec::new();
+		let mut stack: Vec<(Span<'_, C>, String)> =
+			self.roots().map(|span| (span, String::new())).collect();
+		stack.reverse();
+		while let Some((span, prefix)) = stack.pop() {
+			rows.push((
+				format!("{prefix}{}", span.name()),
+				format!("{}", span.calls()),
+				format!("{:?}", span.inclusive()),
+				format!("{:?}", span.exclusive()),
+			));
+			// Children are indented under their parent, with the last child drawn as the end of a branch.
+			let child_prefix = prefix.replace('├', "│").replace('└', " ");
+			let children: Vec<_> = span.children().collect();
+			for (i, child) in children.into_iter().enumerate().rev() {
+				let branch = match i + 1 == span.node().children.len() {
+					true => "└ ",
+					false => "├ ",
+				};
+				stack.push((child, format!("{child_prefix}{branch}")));
+			}
+		}
+		let mut widths = [4, 5, 9, 9];
+		for row in &rows {
+			let cells = [&row.0, &row.1, &row.2, &row.3];
+			for (width, cell) in widths.iter_mut().zip(cells) {
+				*width = (*width).max(cell.chars().count());
+			}
+		}
+		let [name_width, calls_width, inclusive_width, exclusive_width] = widths;
+		write!(
+			f,
+			"{:<name_width$}  {:>calls_width$}  {:>inclusive_width$}  {:>exclusive_width$}",
+			"span", "calls", "inclusive", "exclusive"
+		)?;
+		for (name, calls, inclusive, exclusive) in &rows {
+			let padding = name_width - name.chars().count();
+			write!(
+				f,
+				"\n{name}{:padding$}  {calls:>calls_width$}  {inclusive:>inclusive_width$}  {exclusive:>exclusive_width$}",
+				""
+			)?;
+		}
+		Ok(())
+	}
+}
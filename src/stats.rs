@@ -0,0 +1,240 @@
+/*
+	This file is part of Ticky.
+	Ticky is free software: you can redistribute it and/or modify
+	it under the terms of the GNU Affero General Public License as published by
+	the Free Software Foundation, either version 3 of the License, or
+	(at your option) any later version.
+	Ticky is distributed in the hope that it will be useful,
+	but WITHOUT ANY WARRANTY; without even the implied warranty of
+	MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+	GNU Affero General Public License for more details.
+	You should have received a copy of the GNU Affero General Public License
+	along with Ticky.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::{Clock, Laps, Rounding, ScalarDuration, Stopwatch};
+
+#[derive(Ord, Eq, PartialEq, PartialOrd, Clone, Copy, Debug, Hash)]
+/// Descriptive statistics over a set of laps.
+///
+/// Statistics are calculated in whole nanoseconds, so they are exact (or rounded to the nearest nanosecond) rather than subject to floating-point error. The one exception is the standard deviation of extremely long or widely spread laps, which is too large to calculate exactly with 128-bit integers and falls back to a floating-point approximation.
+///
+/// # Example
+/// ```rust
+/// use core::time::Duration;
+/// use ticky::LapStats;
+///
+/// let laps = vec![2, 4, 4, 4, 5, 5, 7, 9].into_iter().map(Duration::from_secs).collect::<Vec<_>>();
+/// let stats = LapStats::new(&laps).unwrap(); // Calculate statistics over the laps
+/// assert_eq!(stats.count, 8);
+/// assert_eq!(stats.mean, Duration::from_secs(5));
+/// assert_eq!(stats.median, Duration::from_millis(4_500));
+/// assert_eq!(stats.min, Duration::from_secs(2));
+/// assert_eq!(stats.max, Duration::from_secs(9));
+/// assert_eq!(stats.std_dev, Duration::from_nanos(2_138_089_935)); // The sample standard deviation
+/// ```
+pub struct LapStats<D> {
+	/// The number of laps.
+	pub count: usize,
+	/// The total time of all laps.
+	pub total: D,
+	/// The shortest lap.
+	pub min: D,
+	/// The longest lap.
+	pub max: D,
+	/// The mean lap time.
+	pub mean: D,
+	/// The median lap time; with an even number of laps, the mean of the two middle laps.
+	pub median: D,
+	/// The sample standard deviation of the lap times.
+	pub std_dev: D,
+}
+
+impl<D: ScalarDuration + Ord> LapStats<D> {
+	/// Calculates statistics over the laps kept by the given lap recorder, or returns `None` if it keeps no laps.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::LapStats;
+	///
+	/// let laps: Vec<Duration> = Vec::new(); // No laps have been recorded
+	/// assert_eq!(LapStats::new(&laps), None);
+	///
+	/// let laps = vec![Duration::MAX, Duration::MAX / 2, Duration::from_secs(1)]; // Laps too long for exact statistics
+	/// let stats = LapStats::new(&laps).unwrap(); // Calculate statistics over the laps
+	/// let expected = (Duration::MAX / 2).as_secs_f64(); // The standard deviation is half the longest lap
+	/// assert!((stats.std_dev.as_secs_f64() / expected - 1.0).abs() < 1e-9); // The standard deviation is approximate
+	/// ```
+	pub fn new<L: Laps<D>>(laps: &L) -> Option<LapStats<D>> {
+		let count = laps.laps().count();
+		let n = count as i128;
+		let total: i128 = laps.laps().map(|lap| lap.total_nanoseconds()).sum();
+		let (min, max, median) = order_statistics(laps, count)?;
+		let mean = Rounding::Nearest.divide(total, n);
+		let std_dev = match exact_variance(laps, total, n) {
+			Some(variance) => rounded_sqrt(variance).try_into().unwrap_or(i128::MAX),
+			None => approximate_std_dev(laps),
+		};
+		Some(LapStats {
+			count,
+			total: D::from_total_nanoseconds(total),
+			min,
+			max,
+			mean: D::from_total_nanoseconds(mean),
+			median: D::from_total_nanoseconds(median),
+			std_dev: D::from_total_nanoseconds(std_dev),
+		})
+	}
+
+	/// Returns the coefficient of variation: the standard deviation as a fraction of the mean.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::LapStats;
+	///
+	/// let laps = vec![Duration::from_secs(9), Duration::from_secs(11)];
+	/// let stats = LapStats::new(&laps).unwrap(); // Calculate statistics over the laps
+	/// assert!((stats.coefficient_of_variation() - 0.1414).abs() < 0.0001);
+	/// ```
+	pub fn coefficient_of_variation(&self) -> f64 {
+		self.std_dev.total_nanoseconds() as f64 / self.mean.total_nanoseconds() as f64
+	}
+}
+
+/// Returns the shortest lap, the longest lap and the median lap time in nanoseconds, or `None` if there are no laps.
+///
+/// The laps are copied and sorted once, rather than searched separately for each statistic.
+#[cfg(feature = "alloc")]
+fn order_statistics<D: ScalarDuration + Ord, L: Laps<D>>(
+	laps: &L,
+	count: usize,
+) -> Option<(D, D, i128)> {
+	let mut sorted: alloc::vec::Vec<D> = laps.laps().collect();
+	sorted.sort_unstable();
+	let upper = sorted.get(count / 2)?.total_nanoseconds();
+	let median = match count % 2 {
+		0 => Rounding::Nearest.divide(sorted[count / 2 - 1].total_nanoseconds() + upper, 2),
+		_ => upper,
+	};
+	Some((*sorted.first()?, *sorted.last()?, median))
+}
+
+/// Returns the shortest lap, the longest lap and the median lap time in nanoseconds, or `None` if there are no laps.
+#[cfg(not(feature = "alloc"))]
+fn order_statistics<D: ScalarDuration + Ord, L: Laps<D>>(
+	laps: &L,
+	count: usize,
+) -> Option<(D, D, i128)> {
+	let min = laps.laps().min()?;
+	let max = laps.laps().max()?;
+	let upper = laps.nth_fastest_lap(count / 2)?.total_nanoseconds();
+	let median = match count % 2 {
+		0 => {
+			let lower = laps.nth_fastest_lap(count / 2 - 1)?.total_nanoseconds();
+			Rounding::Nearest.divide(lower + upper, 2)
+		}
+		_ => upper,
+	};
+	Some((min, max, median))
+}
+
+/// Returns the square root of a number, rounded to the nearest whole number.
+fn rounded_sqrt(n: u128) -> u128 {
+	let root = n.isqrt();
+	root + u128::from(n - root * root > root)
+}
+
+/// Calculates the sample variance of the laps in square nanoseconds, or returns `None` if it cannot be calculated exactly with integers.
+fn exact_variance<D: ScalarDuration, L: Laps<D>>(laps: &L, total: i128, n: i128) -> Option<u128> {
+	if n < 2 {
+		return Some(0);
+	}
+	// Scaling every deviation from the mean by `n` keeps the calculation in whole numbers.
+	let sum_of_squares = laps.laps().try_fold(0u128, |sum, lap| {
+		let deviation = lap.total_nanoseconds().checked_mul(n)?.checked_sub(total)?;
+		sum.checked_add(deviation.unsigned_abs().checked_pow(2)?)
+	})?;
+	let scale = n.checked_pow(2)?.checked_mul(n - 1)?;
+	Some(Rounding::Nearest.divide(sum_of_squares.try_into().ok()?, scale) as u128)
+}
+
+/// Calculates the sample standard deviation of the laps in nanoseconds, using floating-point numbers.
+fn approximate_std_dev<D: ScalarDuration, L: Laps<D>>(laps: &L) -> i128 {
+	let n = laps.laps().count() as f64;
+	let mean = laps
+		.laps()
+		.map(|lap| lap.total_nanoseconds() as f64)
+		.sum::<f64>()
+		/ n;
+	let sum_of_squares: f64 = laps
+		.laps()
+		.map(|lap| {
+			let deviation = lap.total_nanoseconds() as f64 - mean;
+			deviation * deviation
+		})
+		.sum();
+	// The variance can be too large for any integer type, and without `std` there is no floating-point square root, so it is scaled down by powers of four until it fits, and its square root scaled back up.
+	let mut variance = sum_of_squares / (n - 1.0);
+	let mut scale: i128 = 1;
+	while variance.is_finite() && variance >= u128::MAX as f64 {
+		variance /= 4.0;
+		scale = scale.saturating_mul(2);
+	}
+	i128::try_from(rounded_sqrt(variance as u128))
+		.map_or(i128::MAX, |root| root.saturating_mul(scale))
+}
+
+impl<C: Clock, L: Laps<C::Duration>> Stopwatch<C, L>
+where
+	C::Duration: ScalarDuration + Ord,
+{
+	/// Calculates statistics over the kept laps, or returns `None` if no laps are kept.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()).with_laps(Vec::new()); // Create a new stopwatch that records laps
+	/// sw.start(); // Start the stopwatch
+	/// for millis in [90, 100, 110, 100] {
+	///     clock.advance(Duration::from_millis(millis)); // Move time forward
+	///     sw.lap(); // Record a lap
+	/// }
+	/// let stats = sw.lap_stats().unwrap(); // Calculate statistics over the laps
+	/// assert_eq!(stats.mean, Duration::from_millis(100));
+	/// assert_eq!(stats.median, Duration::from_millis(100));
+	/// assert_eq!(stats.std_dev, Duration::from_nanos(8_164_966));
+	/// ```
+	pub fn lap_stats(&self) -> Option<LapStats<C::Duration>> {
+		LapStats::new(&self.laps)
+	}
+
+	/// Returns the kept lap at the given percentile (from 0 to 100), using the nearest-rank method, or `None` if no laps are kept.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()).with_laps(Vec::new()); // Create a new stopwatch that records laps
+	/// sw.start(); // Start the stopwatch
+	/// for millis in 1..=100 {
+	///     clock.advance(Duration::from_millis(millis)); // Move time forward
+	///     sw.lap(); // Record a lap
+	/// }
+	/// assert_eq!(sw.lap_percentile(50.0), Some(Duration::from_millis(50)));
+	/// assert_eq!(sw.lap_percentile(99.0), Some(Duration::from_millis(99)));
+	/// assert_eq!(sw.lap_percentile(100.0), Some(Duration::from_millis(100)));
+	/// ```
+	pub fn lap_percentile(&self, percentile: f64) -> Option<C::Duration> {
+		let count = self.laps.laps().count();
+		let position = percentile / 100.0 * count as f64;
+		let rank = position as usize + usize::from((position as usize as f64) < position);
+		self.laps.nth_fastest_lap(rank.clamp(1, count.max(1)) - 1)
+	}
+}
@@ -0,0 +1,185 @@
+/*
+	This file is part of Ticky.
+	Ticky is free software: you can redistribute it and/or modify
+	it under the terms of the GNU Affero General Public License as published by
+	the Free Software Foundation, either version 3 of the License, or
+	(at your option) any later version.
+	Ticky is distributed in the hope that it will be useful,
+	but WITHOUT ANY WARRANTY; without even the implied warranty of
+	MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+	GNU Affero General Public License for more details.
+	You should have received a copy of the GNU Affero General Public License
+	along with Ticky.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::{Clock, DefaultClock, ItemTimer, TimingSummary};
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::Stream;
+
+/// A stream that yields each item of the stream it wraps alongside how long it took to produce, created with [`TimedStreamExt::timed`].
+///
+/// An item's time runs from the first poll after the previous item (or the first poll, for the first item) until the item is ready, so it includes time spent waiting to be polled again.
+///
+/// ## Usage
+/// ```rust
+/// use core::pin::Pin;
+/// use core::task::{Context, Poll, Waker};
+/// use core::time::Duration;
+/// use futures_core::Stream;
+/// use ticky::{MockClock, TimedStreamExt};
+///
+/// /// A stream of records, each of which is ready on its second poll.
+/// struct Records {
+///     clock: MockClock,
+///     remaining: Vec<u64>,
+///     waiting: bool,
+/// }
+///
+/// impl Stream for Records {
+///     type Item = u64;
+///
+///     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<u64>> {
+///         self.waiting = !self.waiting;
+///         if self.waiting {
+///             cx.waker().wake_by_ref();
+///             return Poll::Pending;
+///         }
+///         let record = self.remaining.pop();
+///         if let Some(millis) = record {
+///             self.clock.advance(Duration::from_millis(millis)); // Each record takes a different time to fetch
+///         }
+///         Poll::Ready(record)
+///     }
+/// }
+///
+/// let clock = MockClock::new(); // Create a new mock clock
+/// let records = Records { clock: clock.clone(), remaining: vec![20, 500, 10], waiting: false };
+/// let mut timed = records.timed_with_clock(clock.clone()); // Time each record
+/// let mut cx = Context::from_waker(Waker::noop());
+/// let mut times = Vec::new();
+/// loop {
+///     match Pin::new(&mut timed).poll_next(&mut cx) {
+///         Poll::Ready(Some((_, time))) => times.push(time),
+///         Poll::Ready(None) => break,
+///         Poll::Pending => clock.advance(Duration::from_millis(5)), // Wait 5 milliseconds before polling again
+///     }
+/// }
+/// assert_eq!(times, [Duration::from_millis(15), Duration::from_millis(505), Duration::from_millis(25)]);
+/// let summary = timed.summary(); // Summarise once the stream is exhausted
+/// assert_eq!(summary.count, 3);
+/// assert_eq!(summary.slowest, Some((1, Duration::from_millis(505)))); // The second record was the slowest
+/// ```
+pub struct TimedStream<S, C: Clock = DefaultClock> {
+	/// The stream being timed.
+	stream: S,
+	/// The timer for the stream's items.
+	timer: ItemTimer<C>,
+}
+
+impl<S, C: Clock> TimedStream<S, C>
+where
+	C::Duration: Ord,
+{
+	/// Returns a summary of the items produced so far.
+	pub fn summary(&self) -> TimingSummary<C::Duration> {
+		self.timer.summary()
+	}
+}
+
+impl<S: Stream, C: Clock> Stream for TimedStream<S, C>
+where
+	C::Duration: Ord,
+{
+	type Item = (S::Item, C::Duration);
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		// SAFETY: `stream` is never moved out of `self`, and no other field is pinned.
+		let this = unsafe { self.get_unchecked_mut() };
+		this.timer.begin();
+		// SAFETY: `this` is pinned, so `stream` is too.
+		match unsafe { Pin::new_unchecked(&mut this.stream) }.poll_next(cx) {
+			Poll::Ready(item) => {
+				let time = this.timer.finish(item.is_some());
+				Poll::Ready(item.map(|item| (item, time)))
+			}
+			Poll::Pending => Poll::Pending,
+		}
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.stream.size_hint()
+	}
+}
+
+/// Adds methods for timing streams.
+pub trait TimedStreamExt: Stream + Sized {
+	#[cfg(any(feature = "stdtime", all(feature = "hifitime", feature = "std")))]
+	/// Wraps the stream so that it yields each item alongside how long it took to produce, measured with the [`DefaultClock`].
+	///
+	/// # Example
+	/// ```rust
+	/// use core::pin::Pin;
+	/// use core::task::{Context, Poll, Waker};
+	/// use futures_core::Stream;
+	/// use ticky::TimedStreamExt;
+	///
+	/// /// A stream counting down to zero, with every item ready immediately.
+	/// struct Countdown(u32);
+	///
+	/// impl Stream for Countdown {
+	///     type Item = u32;
+	///
+	///     fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<u32>> {
+	///         let next = self.0.checked_sub(1);
+	///         self.0 = next.unwrap_or(0);
+	///         Poll::Ready(next)
+	///     }
+	/// }
+	///
+	/// let mut timed = Countdown(3).timed(); // Time each item
+	/// let mut cx = Context::from_waker(Waker::noop());
+	/// while let Poll::Ready(Some(_)) = Pin::new(&mut timed).poll_next(&mut cx) {}
+	/// assert_eq!(timed.summary().count, 3); // Three items were produced: 2, 1 and 0
+	/// ```
+	fn timed(self) -> TimedStream<Self> {
+		self.timed_with_clock(crate::DEFAULT_CLOCK)
+	}
+
+	/// Wraps the stream so that it yields each item alongside how long it took to produce, measured with the given clock.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::pin::Pin;
+	/// use core::task::{Context, Poll, Waker};
+	/// use core::time::Duration;
+	/// use futures_core::Stream;
+	/// use ticky::{MockClock, TimedStreamExt};
+	///
+	/// /// A stream of a single item, ready immediately.
+	/// struct Once(Option<&'static str>);
+	///
+	/// impl Stream for Once {
+	///     type Item = &'static str;
+	///
+	///     fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<&'static str>> {
+	///         Poll::Ready(self.0.take())
+	///     }
+	/// }
+	///
+	/// let mut timed = Once(Some("a")).timed_with_clock(MockClock::new()); // Time each item with a mock clock
+	/// let mut cx = Context::from_waker(Waker::noop());
+	/// assert_eq!(Pin::new(&mut timed).poll_next(&mut cx), Poll::Ready(Some(("a", Duration::ZERO)))); // No time passed on the mock clock
+	/// ```
+	fn timed_with_clock<C: Clock>(self, clock: C) -> TimedStream<Self, C>
+	where
+		C::Duration: Ord,
+	{
+		TimedStream {
+			stream: self,
+			timer: ItemTimer::new(clock),
+		}
+	}
+}
+
+impl<S: Stream> TimedStreamExt for S {}
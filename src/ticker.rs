@@ -0,0 +1,339 @@
+/*
+	This file is part of Ticky.
+	Ticky is free software: you can redistribute it and/or modify
+	it under the terms of the GNU Affero General Public License as published by
+	the Free Software Foundation, either version 3 of the License, or
+	(at your option) any later version.
+	Ticky is distributed in the hope that it will be useful,
+	but WITHOUT ANY WARRANTY; without even the implied warranty of
+	MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+	GNU Affero General Public License for more details.
+	You should have received a copy of the GNU Affero General Public License
+	along with Ticky.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::{Clock, DefaultClock, ScalarDuration, Stopwatch};
+use core::fmt::{Debug, Formatter};
+
+#[derive(Ord, Eq, PartialEq, PartialOrd, Clone, Copy, Debug, Hash, Default)]
+/// What a [`Ticker`] does when it falls behind, so that one or more ticks are already overdue.
+pub enum MissedTickPolicy {
+	/// Fire every overdue tick as soon as possible, one after another, until the ticker has caught up with its schedule.
+	#[default]
+	Burst,
+	/// Fire only the most recent overdue tick, counting the ones before it as missed, and keep to the original schedule.
+	Skip,
+	/// Fire the overdue tick, and schedule the following ticks one period apart from now, shifting the schedule later. Each whole period the tick is late by counts as a missed tick.
+	Delay,
+}
+
+#[derive(Ord, Eq, PartialEq, PartialOrd, Clone, Copy, Debug, Hash)]
+/// A tick fired by a [`Ticker`].
+pub struct Tick<D> {
+	/// The position of the tick in the schedule, counting from zero; ticks skipped under [`MissedTickPolicy::Skip`] are counted, while ticks missed under [`MissedTickPolicy::Delay`] are not, as the schedule is shifted instead.
+	pub index: u64,
+	/// How long after its scheduled time the tick was fired.
+	pub lateness: D,
+	/// The number of ticks missed just before this one.
+	pub missed: u64,
+}
+
+/// A ticker firing at a fixed period, for running periodic work without drift.
+///
+/// Ticks are scheduled at fixed offsets from when the ticker was created (one period after it, two periods after it, and so on), rather than one period after the previous tick was handled, so time spent handling ticks does not accumulate into drift. When ticks are overdue, the ticker follows its [`MissedTickPolicy`].
+///
+/// ## Usage
+/// ```rust
+/// use core::time::Duration;
+/// use ticky::{MissedTickPolicy, MockClock, Ticker};
+///
+/// let clock = MockClock::new(); // Create a new mock clock
+/// let mut ticker = Ticker::with_clock(Duration::from_millis(100), clock.clone()).with_policy(MissedTickPolicy::Skip); // Tick every 100 milliseconds
+/// assert_eq!(ticker.poll(), None); // The first tick is not due yet
+/// assert_eq!(ticker.until_next(), Duration::from_millis(100));
+/// clock.advance(Duration::from_millis(130)); // Move time forward by 130 milliseconds
+/// let tick = ticker.poll().unwrap(); // The first tick is due
+/// assert_eq!(tick.lateness, Duration::from_millis(30));
+/// assert_eq!(ticker.until_next(), Duration::from_millis(70)); // The next tick is still due 200 milliseconds after the start
+/// clock.advance(Duration::from_millis(320)); // Fall behind by more than two periods
+/// let tick = ticker.poll().unwrap(); // Only the most recent overdue tick is fired
+/// assert_eq!(tick.index, 3);
+/// assert_eq!(tick.missed, 2);
+/// assert_eq!(tick.lateness, Duration::from_millis(50));
+/// assert_eq!(ticker.missed(), 2);
+/// ```
+pub struct Ticker<C: Clock = DefaultClock> {
+	/// The time between ticks.
+	period: C::Duration,
+	/// What to do when ticks are overdue.
+	policy: MissedTickPolicy,
+	/// The stopwatch keeping the time since the ticker was created.
+	stopwatch: Stopwatch<C>,
+	/// When the next tick is due, in nanoseconds since the ticker was created.
+	next: i128,
+	/// The position of the next tick in the schedule.
+	index: u64,
+	/// The number of ticks missed so far.
+	missed: u64,
+}
+
+impl<C: Clock> Clone for Ticker<C>
+where
+	Stopwatch<C>: Clone,
+{
+	fn clone(&self) -> Self {
+		Ticker {
+			period: self.period,
+			policy: self.policy,
+			stopwatch: self.stopwatch.clone(),
+			next: self.next,
+			index: self.index,
+			missed: self.missed,
+		}
+	}
+}
+
+impl<C: Clock> Debug for Ticker<C>
+where
+	C::Duration: Debug,
+	Stopwatch<C>: Debug,
+{
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("Ticker")
+			.field("period", &self.period)
+			.field("policy", &self.policy)
+			.field("stopwatch", &self.stopwatch)
+			.field("next", &self.next)
+			.field("index", &self.index)
+			.field("missed", &self.missed)
+			.finish()
+	}
+}
+
+#[cfg(any(feature = "stdtime", all(feature = "hifitime", feature = "std")))]
+impl Ticker {
+	/// Creates a new ticker firing every `period`, starting one period from now.
+	///
+	/// # Panics
+	/// Panics if the period is not positive.
+	///
+	/// # Example
+	/// ```rust
+	/// use std::time::Duration;
+	/// use ticky::Ticker;
+	///
+	/// let mut ticker = Ticker::new(Duration::from_millis(10)); // Tick every 10 milliseconds
+	/// for _ in 0..3 {
+	///     ticker.wait(); // Wait for the next tick
+	/// }
+	/// assert!(ticker.elapsed().as_millis() >= 30); // At least three periods have passed
+	/// ```
+	pub fn new(period: <DefaultClock as Clock>::Duration) -> Ticker {
+		Ticker::with_clock(period, crate::DEFAULT_CLOCK)
+	}
+}
+
+impl<C: Clock> Ticker<C>
+where
+	C::Duration: ScalarDuration,
+{
+	/// Creates a new ticker firing every `period` according to the given clock, starting one period from now.
+	///
+	/// # Panics
+	/// Panics if the period is not positive.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Ticker};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut ticker = Ticker::with_clock(Duration::from_secs(1), clock.clone()); // Tick every second
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// assert_eq!(ticker.poll().map(|tick| tick.index), Some(0));
+	/// ```
+	pub fn with_clock(period: C::Duration, clock: C) -> Ticker<C> {
+		let nanos = period.total_nanoseconds();
+		assert!(nanos > 0, "a ticker's period must be positive");
+		let mut stopwatch = Stopwatch::with_clock(clock);
+		stopwatch.start();
+		Ticker {
+			period,
+			policy: MissedTickPolicy::default(),
+			stopwatch,
+			next: nanos,
+			index: 0,
+			missed: 0,
+		}
+	}
+
+	/// Sets what the ticker does when ticks are overdue.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MissedTickPolicy, MockClock, Ticker};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut ticker = Ticker::with_clock(Duration::from_millis(100), clock.clone()).with_policy(MissedTickPolicy::Delay); // Tick every 100 milliseconds
+	/// clock.advance(Duration::from_millis(250)); // Fall behind by more than a period
+	/// let tick = ticker.poll().unwrap(); // The overdue tick is fired
+	/// assert_eq!(tick.lateness, Duration::from_millis(150));
+	/// assert_eq!(tick.missed, 1); // It was more than a whole period late
+	/// assert_eq!(ticker.poll(), None); // No other tick is fired …
+	/// assert_eq!(ticker.until_next(), Duration::from_millis(100)); // … and the next one is a full period from now
+	/// ```
+	pub fn with_policy(mut self, policy: MissedTickPolicy) -> Ticker<C> {
+		self.policy = policy;
+		self
+	}
+
+	/// Returns the time between ticks.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Ticker};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let ticker = Ticker::with_clock(Duration::from_millis(100), clock.clone()); // Tick every 100 milliseconds
+	/// assert_eq!(ticker.period(), Duration::from_millis(100));
+	/// ```
+	pub fn period(&self) -> C::Duration {
+		self.period
+	}
+
+	/// Returns the time since the ticker was created.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Ticker};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let ticker = Ticker::with_clock(Duration::from_millis(100), clock.clone()); // Tick every 100 milliseconds
+	/// clock.advance(Duration::from_millis(250)); // Move time forward by 250 milliseconds
+	/// assert_eq!(ticker.elapsed(), Duration::from_millis(250));
+	/// ```
+	pub fn elapsed(&self) -> C::Duration {
+		self.stopwatch.elapsed()
+	}
+
+	/// Returns the number of ticks missed so far.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MissedTickPolicy, MockClock, Ticker};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut skip = Ticker::with_clock(Duration::from_millis(100), clock.clone()).with_policy(MissedTickPolicy::Skip); // Skip overdue ticks
+	/// let mut delay = Ticker::with_clock(Duration::from_millis(100), clock.clone()).with_policy(MissedTickPolicy::Delay); // Delay the schedule instead
+	/// let mut burst = Ticker::with_clock(Duration::from_millis(100), clock.clone()); // Burst through overdue ticks
+	/// clock.advance(Duration::from_millis(450)); // Fall behind by more than three periods
+	/// while skip.poll().is_some() {} // Fire every tick that is due
+	/// while delay.poll().is_some() {}
+	/// while burst.poll().is_some() {}
+	/// assert_eq!(skip.missed(), 3); // The ticks due at 100, 200 and 300 milliseconds were skipped
+	/// assert_eq!(delay.missed(), 3); // The tick was more than three periods late
+	/// assert_eq!(burst.missed(), 0); // Every overdue tick was fired, so none were missed
+	/// ```
+	pub fn missed(&self) -> u64 {
+		self.missed
+	}
+
+	/// Returns the time left until the next tick is due, or zero if it is already due.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Ticker};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut ticker = Ticker::with_clock(Duration::from_millis(100), clock.clone()); // Tick every 100 milliseconds
+	/// clock.advance(Duration::from_millis(30)); // Move time forward by 30 milliseconds
+	/// assert_eq!(ticker.until_next(), Duration::from_millis(70));
+	/// clock.advance(Duration::from_millis(100)); // Move time forward past the first tick
+	/// assert_eq!(ticker.until_next(), Duration::ZERO); // The first tick is due
+	/// ```
+	pub fn until_next(&self) -> C::Duration {
+		let now = self.stopwatch.elapsed().total_nanoseconds();
+		C::Duration::from_total_nanoseconds((self.next - now).max(0))
+	}
+
+	/// Fires the next tick if it is due, or returns `None` if it is not.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Ticker};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut ticker = Ticker::with_clock(Duration::from_millis(100), clock.clone()); // Tick every 100 milliseconds, bursting when behind
+	/// clock.advance(Duration::from_millis(250)); // Fall behind by more than a period
+	/// let lateness: Vec<_> = core::iter::from_fn(|| ticker.poll()).map(|tick| tick.lateness).collect(); // Fire every overdue tick
+	/// assert_eq!(lateness, [Duration::from_millis(150), Duration::from_millis(50)]);
+	/// assert_eq!(ticker.missed(), 0); // No ticks were missed
+	/// ```
+	pub fn poll(&mut self) -> Option<Tick<C::Duration>> {
+		let now = self.stopwatch.elapsed().total_nanoseconds();
+		if now < self.next {
+			return None;
+		}
+		let period = self.period.total_nanoseconds();
+		let mut lateness = now - self.next;
+		let behind = lateness / period;
+		let missed = match self.policy {
+			MissedTickPolicy::Burst => {
+				self.next += period;
+				0
+			}
+			MissedTickPolicy::Skip => {
+				lateness -= behind * period;
+				self.next += (behind + 1) * period;
+				self.index += behind as u64;
+				behind as u64
+			}
+			MissedTickPolicy::Delay => {
+				self.next = now + period;
+				behind as u64
+			}
+		};
+		let tick = Tick {
+			index: self.index,
+			lateness: C::Duration::from_total_nanoseconds(lateness),
+			missed,
+		};
+		self.index += 1;
+		self.missed += missed;
+		Some(tick)
+	}
+
+	#[cfg(feature = "std")]
+	/// Sleeps until the next tick is due, then fires it.
+	///
+	/// This sleeps the current thread for as long as the clock says is left, so it is only suitable for clocks that follow real time.
+	///
+	/// # Example
+	/// ```rust
+	/// use std::time::Duration;
+	/// use ticky::Ticker;
+	///
+	/// let mut ticker = Ticker::new(Duration::from_millis(10)); // Tick every 10 milliseconds
+	/// let tick = ticker.wait(); // Wait for the first tick
+	/// assert_eq!(tick.index, 0);
+	/// assert!(ticker.elapsed() >= Duration::from_millis(10)); // The tick was not fired early
+	/// ```
+	pub fn wait(&mut self) -> Tick<C::Duration> {
+		loop {
+			if let Some(tick) = self.poll() {
+				return tick;
+			}
+			std::thread::sleep(
+				<core::time::Duration as ScalarDuration>::from_total_nanoseconds(
+					self.until_next().total_nanoseconds(),
+				),
+			);
+		}
+	}
+}
@@ -0,0 +1,356 @@
+/*
+	This file is part of Ticky.
+	Ticky is free software: you can redistribute it and/or modify
+	it under the terms of the GNU Affero General Public License as published by
+	the Free Software Foundation, either version 3 of the License, or
+	(at your option) any later version.
+	Ticky is distributed in the hope that it will be useful,
+	but WITHOUT ANY WARRANTY; without even the implied warranty of
+	MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+	GNU Affero General Public License for more details.
+	You should have received a copy of the GNU Affero General Public License
+	along with Ticky.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::{Clock, ClockDuration, DefaultClock, ScalarDuration, Stopwatch, TickyError};
+use core::fmt::{Debug, Formatter};
+
+/// A countdown timer, counting down from a duration to zero.
+///
+/// The time that has passed is kept by a [`Stopwatch`], so a timer reads time from the same clocks, and can be paused and resumed in the same way.
+///
+/// ## Usage
+/// ```rust
+/// use core::time::Duration;
+/// use ticky::{MockClock, Timer};
+///
+/// let clock = MockClock::new(); // Create a new mock clock
+/// let mut timer = Timer::with_clock(Duration::from_secs(10), clock.clone()); // Create a new 10 second timer sharing the mock clock
+/// timer.resume(); // Start counting down
+/// clock.advance(Duration::from_secs(4)); // Move time forward by 4 seconds
+/// assert_eq!(timer.remaining(), Duration::from_secs(6));
+/// timer.pause(); // Pause the countdown
+/// clock.advance(Duration::from_secs(60)); // Time passing while paused is not counted
+/// assert_eq!(timer.remaining(), Duration::from_secs(6));
+/// timer.resume(); // Resume the countdown
+/// clock.advance(Duration::from_secs(7)); // Move time forward by 7 seconds
+/// assert_eq!(timer.is_expired(), true);
+/// assert_eq!(timer.remaining(), Duration::ZERO);
+/// assert_eq!(timer.overrun(), Duration::from_secs(1)); // The timer expired 1 second ago
+/// ```
+pub struct Timer<C: Clock = DefaultClock> {
+	/// The duration to count down from.
+	pub duration: C::Duration,
+	/// The stopwatch keeping the time that has passed.
+	pub stopwatch: Stopwatch<C>,
+}
+
+impl<C: Clock> Clone for Timer<C>
+where
+	Stopwatch<C>: Clone,
+{
+	fn clone(&self) -> Self {
+		Timer {
+			duration: self.duration,
+			stopwatch: self.stopwatch.clone(),
+		}
+	}
+}
+
+impl<C: Clock> Copy for Timer<C> where Stopwatch<C>: Copy {}
+
+impl<C: Clock> Debug for Timer<C>
+where
+	C::Duration: Debug,
+	Stopwatch<C>: Debug,
+{
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("Timer")
+			.field("duration", &self.duration)
+			.field("stopwatch", &self.stopwatch)
+			.finish()
+	}
+}
+
+#[cfg(any(feature = "stdtime", all(feature = "hifitime", feature = "std")))]
+impl Timer {
+	/// Creates a new timer counting down from the given duration, without starting it.
+	///
+	/// # Example
+	/// ```rust
+	/// use std::time::Duration;
+	/// use ticky::Timer;
+	///
+	/// let mut timer = Timer::new(Duration::from_millis(100)); // Create a new 100 millisecond timer
+	/// std::thread::sleep(Duration::from_millis(200)); // Wait for 200 milliseconds
+	/// assert_eq!(timer.remaining(), Duration::from_millis(100)); // The timer has not been started
+	/// timer.resume(); // Start counting down
+	/// std::thread::sleep(Duration::from_millis(200)); // Wait for 200 milliseconds
+	/// assert_eq!(timer.is_expired(), true);
+	/// ```
+	pub const fn new(duration: <DefaultClock as Clock>::Duration) -> Timer {
+		Timer::with_clock(duration, crate::DEFAULT_CLOCK)
+	}
+
+	/// Creates a new timer counting down from the given duration, and starts it.
+	///
+	/// # Example
+	/// ```rust
+	/// use std::time::Duration;
+	/// use ticky::Timer;
+	///
+	/// let timer = Timer::start_new(Duration::from_secs(60)); // Create a new 1 minute timer, and start it
+	/// assert_eq!(timer.is_running(), true);
+	/// assert_eq!(timer.is_expired(), false);
+	/// ```
+	pub fn start_new(duration: <DefaultClock as Clock>::Duration) -> Timer {
+		let mut timer = Timer::new(duration);
+		timer.resume();
+		timer
+	}
+}
+
+impl<C: Clock> Timer<C> {
+	/// Creates a new timer counting down from the given duration and reading time from the given clock, without starting it.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Timer};
+	///
+	/// let timer = Timer::with_clock(Duration::from_secs(10), MockClock::new()); // Create a new 10 second timer using a mock clock
+	/// assert_eq!(timer.is_running(), false);
+	/// ```
+	pub const fn with_clock(duration: C::Duration, clock: C) -> Timer<C> {
+		Timer {
+			duration,
+			stopwatch: Stopwatch::with_clock(clock),
+		}
+	}
+
+	/// Starts (or resumes) counting down.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Timer};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut timer = Timer::with_clock(Duration::from_secs(10), clock.clone()); // Create a new 10 second timer sharing the mock clock
+	/// timer.resume(); // Start counting down
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// assert_eq!(timer.remaining(), Duration::from_secs(9));
+	/// ```
+	pub fn resume(&mut self) {
+		if !self.stopwatch.is_running {
+			self.stopwatch.start();
+		}
+	}
+
+	/// Pauses counting down.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Timer};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut timer = Timer::with_clock(Duration::from_secs(10), clock.clone()); // Create a new 10 second timer sharing the mock clock
+	/// timer.resume(); // Start counting down
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// timer.pause(); // Pause the countdown
+	/// clock.advance(Duration::from_secs(1)); // Time passing while paused is not counted
+	/// assert_eq!(timer.remaining(), Duration::from_secs(9));
+	/// timer.pause(); // Pausing again has no effect
+	/// assert_eq!(timer.remaining(), Duration::from_secs(9));
+	/// ```
+	pub fn pause(&mut self) {
+		self.stopwatch.stop();
+	}
+
+	/// Returns whether the timer is counting down.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Timer};
+	///
+	/// let mut timer = Timer::with_clock(Duration::from_secs(10), MockClock::new()); // Create a new 10 second timer using a mock clock
+	/// assert_eq!(timer.is_running(), false); // The timer has not been started
+	/// timer.resume(); // Start counting down
+	/// assert_eq!(timer.is_running(), true);
+	/// timer.pause(); // Pause the countdown
+	/// assert_eq!(timer.is_running(), false);
+	/// ```
+	pub fn is_running(&self) -> bool {
+		self.stopwatch.is_running
+	}
+
+	/// Stops the timer and winds it back to its full duration.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Timer};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut timer = Timer::with_clock(Duration::from_secs(10), clock.clone()); // Create a new 10 second timer sharing the mock clock
+	/// timer.resume(); // Start counting down
+	/// clock.advance(Duration::from_secs(15)); // Move time forward by 15 seconds
+	/// timer.reset(); // Reset the timer
+	/// assert_eq!(timer.remaining(), Duration::from_secs(10));
+	/// assert_eq!(timer.is_running(), false);
+	/// ```
+	pub fn reset(&mut self) {
+		self.stopwatch.reset();
+	}
+
+	/// Winds the timer back to its full duration, and starts counting down again.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Timer};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut timer = Timer::with_clock(Duration::from_secs(10), clock.clone()); // Create a new 10 second timer sharing the mock clock
+	/// timer.resume(); // Start counting down
+	/// clock.advance(Duration::from_secs(15)); // Move time forward by 15 seconds
+	/// timer.restart(); // Restart the timer
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// assert_eq!(timer.remaining(), Duration::from_secs(9));
+	/// ```
+	pub fn restart(&mut self) {
+		self.stopwatch.restart();
+	}
+
+	/// Adds time to the countdown, whether or not the timer has expired.
+	///
+	/// # Panics
+	/// Panics if the new duration overflows; see [`try_extend`](Timer::try_extend) to handle this instead.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Timer};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut timer = Timer::with_clock(Duration::from_secs(10), clock.clone()); // Create a new 10 second timer sharing the mock clock
+	/// timer.resume(); // Start counting down
+	/// clock.advance(Duration::from_secs(12)); // Move time forward by 12 seconds
+	/// timer.extend(Duration::from_secs(5)); // Give the timer 5 more seconds
+	/// assert_eq!(timer.is_expired(), false);
+	/// assert_eq!(timer.remaining(), Duration::from_secs(3));
+	/// ```
+	pub fn extend(&mut self, by: C::Duration) {
+		self.duration += by;
+	}
+
+	/// Adds time to the countdown, whether or not the timer has expired, or returns an error if the new duration overflows.
+	///
+	/// If an error is returned, the timer is left unchanged.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Timer, TickyError};
+	///
+	/// let mut timer = Timer::with_clock(Duration::from_secs(10), MockClock::new()); // Create a new 10 second timer using a mock clock
+	/// timer.try_extend(Duration::from_secs(5))?; // Give the timer 5 more seconds
+	/// assert_eq!(timer.duration, Duration::from_secs(15));
+	/// assert_eq!(timer.try_extend(Duration::MAX), Err(TickyError::Overflow)); // The new duration cannot be represented
+	/// assert_eq!(timer.duration, Duration::from_secs(15));
+	/// # Ok::<(), TickyError>(())
+	/// ```
+	pub fn try_extend(&mut self, by: C::Duration) -> Result<(), TickyError> {
+		self.duration = self.duration.checked_add(by).ok_or(TickyError::Overflow)?;
+		Ok(())
+	}
+
+	/// Returns the time that has passed while the timer was counting down.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Timer};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut timer = Timer::with_clock(Duration::from_secs(10), clock.clone()); // Create a new 10 second timer sharing the mock clock
+	/// timer.resume(); // Start counting down
+	/// clock.advance(Duration::from_secs(12)); // Move time forward by 12 seconds
+	/// assert_eq!(timer.elapsed(), Duration::from_secs(12)); // The elapsed time keeps counting after the timer expires
+	/// ```
+	pub fn elapsed(&self) -> C::Duration {
+		self.stopwatch.elapsed()
+	}
+}
+
+impl<C: Clock> Timer<C>
+where
+	C::Duration: ScalarDuration,
+{
+	/// Returns how far the elapsed time is from the duration, in nanoseconds; positive before the timer expires.
+	fn remaining_nanos(&self) -> i128 {
+		self.duration.total_nanoseconds() - self.elapsed().total_nanoseconds()
+	}
+
+	/// Returns the time left before the timer expires, or zero if it has expired.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Timer};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut timer = Timer::with_clock(Duration::from_secs(10), clock.clone()); // Create a new 10 second timer sharing the mock clock
+	/// timer.resume(); // Start counting down
+	/// clock.advance(Duration::from_millis(2_500)); // Move time forward by 2.5 seconds
+	/// assert_eq!(timer.remaining(), Duration::from_millis(7_500));
+	/// ```
+	pub fn remaining(&self) -> C::Duration {
+		match self.remaining_nanos() {
+			nanos if nanos > 0 => C::Duration::from_total_nanoseconds(nanos),
+			_ => C::Duration::ZERO,
+		}
+	}
+
+	/// Returns whether the timer has counted all the way down.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Timer};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut timer = Timer::with_clock(Duration::from_secs(10), clock.clone()); // Create a new 10 second timer sharing the mock clock
+	/// timer.resume(); // Start counting down
+	/// clock.advance(Duration::from_secs(9)); // Move time forward by 9 seconds
+	/// assert_eq!(timer.is_expired(), false);
+	/// clock.advance(Duration::from_secs(1)); // Move time forward by 1 second
+	/// assert_eq!(timer.is_expired(), true); // The timer expires as soon as it reaches zero
+	/// ```
+	pub fn is_expired(&self) -> bool {
+		self.remaining_nanos() <= 0
+	}
+
+	/// Returns how long ago the timer expired, or zero if it has not expired.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Timer};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut timer = Timer::with_clock(Duration::from_secs(10), clock.clone()); // Create a new 10 second timer sharing the mock clock
+	/// timer.resume(); // Start counting down
+	/// assert_eq!(timer.overrun(), Duration::ZERO); // The timer has not expired
+	/// clock.advance(Duration::from_secs(25)); // Move time forward by 25 seconds
+	/// assert_eq!(timer.overrun(), Duration::from_secs(15));
+	/// ```
+	pub fn overrun(&self) -> C::Duration {
+		match self.remaining_nanos() {
+			nanos if nanos < 0 => C::Duration::from_total_nanoseconds(-nanos),
+			_ => C::Duration::ZERO,
+		}
+	}
+}
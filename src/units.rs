@@ -0,0 +1,408 @@
+/*
+	This file is part of Ticky.
+	Ticky is free software: you can redistribute it and/or modify
+	it under the terms of the GNU Affero General Public License as published by
+	the Free Software Foundation, either version 3 of the License, or
+	(at your option) any later version.
+	Ticky is distributed in the hope that it will be useful,
+	but WITHOUT ANY WARRANTY; without even the implied warranty of
+	MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+	GNU Affero General Public License for more details.
+	You should have received a copy of the GNU Affero General Public License
+	along with Ticky.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::{Clock, ScalarDuration, Stopwatch, TickyError};
+use core::fmt::{Display, Formatter};
+
+#[derive(Ord, Eq, PartialEq, PartialOrd, Clone, Copy, Debug, Hash)]
+/// A unit of time that a duration can be expressed in.
+pub enum Unit {
+	/// One nanosecond.
+	Nanosecond,
+	/// One thousand nanoseconds.
+	Microsecond,
+	/// One thousand microseconds.
+	Millisecond,
+	/// One thousand milliseconds.
+	Second,
+	/// Sixty seconds.
+	Minute,
+	/// Sixty minutes.
+	Hour,
+}
+
+#[derive(Ord, Eq, PartialEq, PartialOrd, Clone, Copy, Debug, Hash, Default)]
+/// How to round a duration that is not a whole number of some [`Unit`].
+pub enum Rounding {
+	/// Round towards negative infinity.
+	#[default]
+	Floor,
+	/// Round towards positive infinity.
+	Ceil,
+	/// Round to the nearest whole number, with halfway cases rounded away from zero.
+	Nearest,
+	/// Round towards zero.
+	Truncate,
+}
+
+impl Unit {
+	/// Returns the length of the unit in nanoseconds.
+	///
+	/// # Example
+	/// ```rust
+	/// use ticky::Unit;
+	///
+	/// assert_eq!(Unit::Millisecond.nanoseconds(), 1_000_000);
+	/// assert_eq!(Unit::Hour.nanoseconds(), 3_600_000_000_000);
+	/// ```
+	pub const fn nanoseconds(self) -> i128 {
+		match self {
+			Unit::Nanosecond => 1,
+			Unit::Microsecond => 1_000,
+			Unit::Millisecond => 1_000_000,
+			Unit::Second => 1_000_000_000,
+			Unit::Minute => 60_000_000_000,
+			Unit::Hour => 3_600_000_000_000,
+		}
+	}
+
+	/// Returns the given duration as a whole number of this unit, rounded as specified.
+	///
+	/// The result depends only on the length of the duration, not on the type used to represent it.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{Rounding, Unit};
+	///
+	/// let dur = Duration::from_millis(1_500); // 1.5 seconds
+	/// assert_eq!(Unit::Second.whole(dur, Rounding::Floor), 1);
+	/// assert_eq!(Unit::Second.whole(dur, Rounding::Ceil), 2);
+	/// assert_eq!(Unit::Second.whole(dur, Rounding::Nearest), 2);
+	/// assert_eq!(Unit::Second.whole(dur, Rounding::Truncate), 1);
+	/// ```
+	pub fn whole<D: ScalarDuration>(self, dur: D, rounding: Rounding) -> i128 {
+		rounding.divide(dur.total_nanoseconds(), self.nanoseconds())
+	}
+
+	/// Returns the given duration as a fractional number of this unit.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::Unit;
+	///
+	/// let dur = Duration::from_micros(2_500); // 2.5 milliseconds
+	/// assert_eq!(Unit::Millisecond.fractional(dur), 2.5);
+	/// ```
+	pub fn fractional<D: ScalarDuration>(self, dur: D) -> f64 {
+		let nanos = dur.total_nanoseconds();
+		let unit = self.nanoseconds();
+		(nanos / unit) as f64 + (nanos % unit) as f64 / unit as f64
+	}
+}
+
+impl Rounding {
+	/// Divides one number of nanoseconds by another, rounding the quotient.
+	pub(crate) fn divide(self, dividend: i128, divisor: i128) -> i128 {
+		let quotient = dividend / divisor;
+		let remainder = dividend % divisor;
+		match self {
+			Rounding::Floor if remainder < 0 => quotient - 1,
+			Rounding::Ceil if remainder > 0 => quotient + 1,
+			Rounding::Nearest if remainder.unsigned_abs() * 2 >= divisor.unsigned_abs() => {
+				quotient + dividend.signum()
+			}
+			_ => quotient,
+		}
+	}
+}
+
+impl<C: Clock, L> Display for Stopwatch<C, L>
+where
+	C::Duration: ScalarDuration,
+{
+	fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+		let elapsed_ms = Unit::Millisecond.whole(self.elapsed(), Rounding::Floor);
+		write!(f, "{elapsed_ms}ms")
+	}
+}
+
+impl<C: Clock, L> Stopwatch<C, L>
+where
+	C::Duration: ScalarDuration,
+{
+	/// Returns the total elapsed time as a whole number of the given unit, rounded as specified.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Rounding, Stopwatch, Unit};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
+	/// sw.start(); // Start the stopwatch
+	/// clock.advance(Duration::from_millis(1_500)); // Move time forward by 1.5 seconds
+	/// sw.stop(); // Stop the stopwatch
+	/// assert_eq!(sw.elapsed_in(Unit::Second, Rounding::Floor), 1);
+	/// assert_eq!(sw.elapsed_in(Unit::Second, Rounding::Ceil), 2);
+	/// assert_eq!(sw.elapsed_in(Unit::Second, Rounding::Nearest), 2);
+	/// assert_eq!(sw.elapsed_in(Unit::Millisecond, Rounding::Floor), 1_500);
+	/// ```
+	pub fn elapsed_in(&self, unit: Unit, rounding: Rounding) -> i128 {
+		unit.whole(self.elapsed(), rounding)
+	}
+
+	/// Returns the total elapsed time as a whole number of the given unit, rounded as specified, or an error if it could not be measured.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Rounding, Stopwatch, Unit};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
+	/// sw.start(); // Start the stopwatch
+	/// clock.advance(Duration::from_secs(90)); // Move time forward by 90 seconds
+	/// assert_eq!(sw.try_elapsed_in(Unit::Minute, Rounding::Nearest), Ok(2));
+	/// ```
+	pub fn try_elapsed_in(&self, unit: Unit, rounding: Rounding) -> Result<i128, TickyError> {
+		Ok(unit.whole(self.try_elapsed()?, rounding))
+	}
+
+	/// Returns the total elapsed time as a fractional number of the given unit.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch, Unit};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
+	/// sw.start(); // Start the stopwatch
+	/// clock.advance(Duration::from_secs(90)); // Move time forward by 90 seconds
+	/// assert_eq!(sw.elapsed_in_f64(Unit::Minute), 1.5);
+	/// ```
+	pub fn elapsed_in_f64(&self, unit: Unit) -> f64 {
+		unit.fractional(self.elapsed())
+	}
+
+	/// Returns the total elapsed time in fractional milliseconds.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
+	/// sw.start(); // Start the stopwatch
+	/// clock.advance(Duration::from_millis(1_000)); // Move time forward by 1 second
+	/// sw.stop(); // Stop the stopwatch
+	/// assert_eq!(sw.elapsed_ms(), 1_000.0);
+	/// ```
+	pub fn elapsed_ms(&mut self) -> f64 {
+		self.elapsed_in_f64(Unit::Millisecond)
+	}
+
+	/// Returns the total elapsed time in whole milliseconds, rounded down.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
+	/// sw.start(); // Start the stopwatch
+	/// clock.advance(Duration::from_millis(1_000)); // Move time forward by 1 second
+	/// sw.stop(); // Stop the stopwatch
+	/// assert_eq!(sw.elapsed_ms_whole(), 1_000);
+	/// ```
+	pub fn elapsed_ms_whole(&mut self) -> u128 {
+		non_negative(self.elapsed_in(Unit::Millisecond, Rounding::Floor))
+	}
+
+	/// Returns the total elapsed time in fractional microseconds.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
+	/// sw.start(); // Start the stopwatch
+	/// clock.advance(Duration::from_micros(1_000_000)); // Move time forward by 1 second
+	/// sw.stop(); // Stop the stopwatch
+	/// assert_eq!(sw.elapsed_us(), 1_000_000.0);
+	/// ```
+	pub fn elapsed_us(&mut self) -> f64 {
+		self.elapsed_in_f64(Unit::Microsecond)
+	}
+
+	/// Returns the total elapsed time in whole microseconds, rounded down.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
+	/// sw.start(); // Start the stopwatch
+	/// clock.advance(Duration::from_micros(1_000_000)); // Move time forward by 1 second
+	/// sw.stop(); // Stop the stopwatch
+	/// assert_eq!(sw.elapsed_us_whole(), 1_000_000);
+	/// ```
+	pub fn elapsed_us_whole(&mut self) -> u128 {
+		non_negative(self.elapsed_in(Unit::Microsecond, Rounding::Floor))
+	}
+
+	/// Returns the total elapsed time in fractional nanoseconds.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
+	/// sw.start(); // Start the stopwatch
+	/// clock.advance(Duration::from_nanos(1_000_000_000)); // Move time forward by 1 second
+	/// sw.stop(); // Stop the stopwatch
+	/// assert_eq!(sw.elapsed_ns(), 1_000_000_000.0);
+	/// ```
+	pub fn elapsed_ns(&mut self) -> f64 {
+		self.elapsed_in_f64(Unit::Nanosecond)
+	}
+
+	/// Returns the total elapsed time in whole nanoseconds.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
+	/// sw.start(); // Start the stopwatch
+	/// clock.advance(Duration::from_nanos(1_000_000_000)); // Move time forward by 1 second
+	/// sw.stop(); // Stop the stopwatch
+	/// assert_eq!(sw.elapsed_ns_whole(), 1_000_000_000);
+	/// ```
+	pub fn elapsed_ns_whole(&mut self) -> u128 {
+		non_negative(self.elapsed_in(Unit::Nanosecond, Rounding::Floor))
+	}
+
+	/// Returns the total elapsed time in fractional seconds.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
+	/// sw.start(); // Start the stopwatch
+	/// clock.advance(Duration::from_millis(1_500)); // Move time forward by 1.5 seconds
+	/// sw.stop(); // Stop the stopwatch
+	/// assert_eq!(sw.elapsed_s(), 1.5);
+	/// ```
+	pub fn elapsed_s(&mut self) -> f64 {
+		self.elapsed_in_f64(Unit::Second)
+	}
+
+	/// Returns the total elapsed time in whole seconds, rounded down.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let clock = MockClock::new(); // Create a new mock clock
+	/// let mut sw = Stopwatch::with_clock(clock.clone()); // Create a new stopwatch sharing the mock clock
+	/// sw.start(); // Start the stopwatch
+	/// clock.advance(Duration::from_millis(1_500)); // Move time forward by 1.5 seconds
+	/// sw.stop(); // Stop the stopwatch
+	/// assert_eq!(sw.elapsed_s_whole(), 1); // Rounded down
+	/// ```
+	pub fn elapsed_s_whole(&self) -> u64 {
+		non_negative(self.elapsed_in(Unit::Second, Rounding::Floor))
+			.try_into()
+			.unwrap_or(u64::MAX)
+	}
+
+	/// Returns the total elapsed time in whole milliseconds, rounded down, or an error if it could not be measured or is negative.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let mut sw = Stopwatch::with_clock(MockClock::new()); // Create a new stopwatch using a mock clock
+	/// sw.elapsed = Duration::from_micros(1_500_500); // Pretend the stopwatch has been running for 1.5005 seconds
+	/// assert_eq!(sw.try_elapsed_ms_whole(), Ok(1_500));
+	/// ```
+	pub fn try_elapsed_ms_whole(&self) -> Result<u128, TickyError> {
+		try_non_negative(self.try_elapsed_in(Unit::Millisecond, Rounding::Floor)?)
+	}
+
+	/// Returns the total elapsed time in whole microseconds, rounded down, or an error if it could not be measured or is negative.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let mut sw = Stopwatch::with_clock(MockClock::new()); // Create a new stopwatch using a mock clock
+	/// sw.elapsed = Duration::from_nanos(1_500_500); // Pretend the stopwatch has been running for 1.5005 milliseconds
+	/// assert_eq!(sw.try_elapsed_us_whole(), Ok(1_500));
+	/// ```
+	pub fn try_elapsed_us_whole(&self) -> Result<u128, TickyError> {
+		try_non_negative(self.try_elapsed_in(Unit::Microsecond, Rounding::Floor)?)
+	}
+
+	/// Returns the total elapsed time in whole nanoseconds, or an error if it could not be measured or is negative.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let mut sw = Stopwatch::with_clock(MockClock::new()); // Create a new stopwatch using a mock clock
+	/// sw.elapsed = Duration::from_nanos(1_500); // Pretend the stopwatch has been running for 1.5 microseconds
+	/// assert_eq!(sw.try_elapsed_ns_whole(), Ok(1_500));
+	/// ```
+	pub fn try_elapsed_ns_whole(&self) -> Result<u128, TickyError> {
+		try_non_negative(self.try_elapsed_in(Unit::Nanosecond, Rounding::Floor)?)
+	}
+
+	/// Returns the total elapsed time in whole seconds, rounded down, or an error if it could not be measured, is negative, or is too large.
+	///
+	/// # Example
+	/// ```rust
+	/// use core::time::Duration;
+	/// use ticky::{MockClock, Stopwatch};
+	///
+	/// let mut sw = Stopwatch::with_clock(MockClock::new()); // Create a new stopwatch using a mock clock
+	/// sw.elapsed = Duration::from_millis(90_500); // Pretend the stopwatch has been running for 90.5 seconds
+	/// assert_eq!(sw.try_elapsed_s_whole(), Ok(90));
+	/// ```
+	pub fn try_elapsed_s_whole(&self) -> Result<u64, TickyError> {
+		try_non_negative(self.try_elapsed_in(Unit::Second, Rounding::Floor)?)?
+			.try_into()
+			.map_err(|_| TickyError::Overflow)
+	}
+}
+
+/// Converts a whole number of some unit to an unsigned number, clamping negative numbers to zero.
+fn non_negative(count: i128) -> u128 {
+	count.max(0) as u128
+}
+
+/// Converts a whole number of some unit to an unsigned number, or returns an error if it is negative.
+fn try_non_negative(count: i128) -> Result<u128, TickyError> {
+	count.try_into().map_err(|_| TickyError::NegativeDuration)
+}